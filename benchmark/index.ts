@@ -98,7 +98,55 @@ function suite(tzid: string) {
   ];
 }
 
+function betweenSuite(frequency: Rust.Frequency) {
+  const set = new Rust.RRuleSet({
+    dtstart: new Rust.DtStart(
+      Rust.DateTime.local(2015, 1, 1, 0, 0, 0),
+      'Europe/London',
+    ),
+    rrules: [new Rust.RRule(frequency)],
+  });
+  set.cache.disable();
+
+  const after = Rust.DateTime.local(2024, 1, 1, 0, 0, 0);
+  const before = Rust.DateTime.local(2024, 1, 1, 6, 0, 0);
+  const afterTimestamp = Date.UTC(2024, 0, 1, 0, 0, 0);
+  const beforeTimestamp = Date.UTC(2024, 0, 1, 6, 0, 0);
+
+  return [
+    b.add('rruleSet.between() (rust)', () => {
+      set.between(after, before);
+    }),
+    // Previous behavior: every occurrence since DTSTART is visited
+    b.add('rruleSet.between() (rust, scan from dtstart)', () => {
+      const dates = [];
+
+      for (const date of set) {
+        const timestamp = date.toTimestamp();
+
+        if (timestamp >= beforeTimestamp) {
+          break;
+        }
+
+        if (timestamp > afterTimestamp) {
+          dates.push(date);
+        }
+      }
+    }),
+    b.cycle(),
+    b.complete(),
+  ];
+}
+
 Promise.all([
   b.suite('UTC TZ', ...suite('UTC')),
   b.suite('Other TZ', ...suite('Pacific/Kiritimati')),
+  b.suite(
+    'Between (hourly since 2015)',
+    ...betweenSuite(Rust.Frequency.Hourly),
+  ),
+  b.suite(
+    'Between (minutely since 2015)',
+    ...betweenSuite(Rust.Frequency.Minutely),
+  ),
 ]).catch(console.error);
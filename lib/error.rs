@@ -0,0 +1,167 @@
+use std::fmt;
+
+/// Position of a token in the parsed input. Lines and columns are 1-based,
+/// `end` is the column of the last character of the token.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Location {
+  pub line: usize,
+  pub start: usize,
+  pub end: usize,
+  pub token: String,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Error {
+  /// A content line is not a `NAME[;PARAM=VALUE...]:VALUE` pair.
+  InvalidProperty(String),
+  /// A parameter is not a `NAME=VALUE` pair.
+  InvalidParameter(String),
+  /// A parameter list mixes named and unnamed values.
+  InvalidParameters(String),
+  /// A component is not terminated by its END property.
+  UnclosedComponent(String),
+  /// An END property does not terminate the current component.
+  UnexpectedEnd(String),
+  /// A property is given where another one is expected, e.g. DTSTART parsed
+  /// as RRULE.
+  UnexpectedProperty(String),
+  /// The value of a property can't be parsed.
+  InvalidPropertyValue { property: String, value: String },
+  /// The value of a parameter or rule part can't be parsed.
+  InvalidValue { name: String, value: String },
+  /// TZID names an unknown time zone.
+  InvalidTimezone(String),
+  /// The value of a property does not match its VALUE parameter.
+  ValueTypeMismatch(String),
+  /// The values of a property do not share the same value type.
+  MixedValueTypes(String),
+  /// A property or rule part does not match the value type of DTSTART.
+  DtStartValueTypeMismatch(String),
+  /// A required property or rule part is missing.
+  MissingProperty(String),
+  /// A property which may occur once occurs multiple times.
+  DuplicateProperty(String),
+  /// A local time is given without TZID.
+  MissingTimezone(String),
+  /// A local time falls into a DST gap of the time zone.
+  NonexistentLocalTime { datetime: String, tzid: String },
+  /// A local time falls into a DST overlap of the time zone.
+  AmbiguousLocalTime { datetime: String, tzid: String },
+  /// The rule is rejected by the recurrence engine.
+  Validation(String),
+  /// The operation requires a finite set.
+  InfiniteSet,
+  /// An error raised by a token of the parsed input.
+  Located {
+    error: Box<Error>,
+    location: Location,
+  },
+}
+
+impl Error {
+  /// Returns a stable identifier of the error kind, which does not change
+  /// along with the message.
+  pub fn code(&self) -> &'static str {
+    match self {
+      Error::InvalidProperty(_)
+      | Error::InvalidParameter(_)
+      | Error::InvalidParameters(_)
+      | Error::UnclosedComponent(_)
+      | Error::UnexpectedEnd(_) => "PARSE_ERROR",
+      Error::UnexpectedProperty(_) => "UNEXPECTED_PROPERTY",
+      Error::InvalidPropertyValue { .. } | Error::InvalidValue { .. } => "INVALID_VALUE",
+      Error::InvalidTimezone(_) => "INVALID_TIMEZONE",
+      Error::ValueTypeMismatch(_)
+      | Error::MixedValueTypes(_)
+      | Error::DtStartValueTypeMismatch(_) => "VALUE_TYPE_MISMATCH",
+      Error::MissingProperty(_) => "MISSING_PROPERTY",
+      Error::DuplicateProperty(_) => "DUPLICATE_PROPERTY",
+      Error::MissingTimezone(_) => "MISSING_TIMEZONE",
+      Error::NonexistentLocalTime { .. } => "NONEXISTENT_LOCAL_TIME",
+      Error::AmbiguousLocalTime { .. } => "AMBIGUOUS_LOCAL_TIME",
+      Error::Validation(_) => "VALIDATION_ERROR",
+      Error::InfiniteSet => "INFINITE_SET",
+      Error::Located { error, .. } => error.code(),
+    }
+  }
+
+  /// Returns the location of the token which raised the error, if known.
+  pub fn location(&self) -> Option<&Location> {
+    match self {
+      Error::Located { location, .. } => Some(location),
+      _ => None,
+    }
+  }
+
+  /// Attaches `location` to the error, keeping the location it already has.
+  pub fn at(self, location: Location) -> Self {
+    match self {
+      Error::Located { .. } => self,
+      error => Error::Located {
+        error: Box::new(error),
+        location,
+      },
+    }
+  }
+
+  pub fn invalid_value(name: &str, value: &str) -> Self {
+    Error::InvalidValue {
+      name: name.to_string(),
+      value: value.to_string(),
+    }
+  }
+
+  pub fn invalid_property_value(property: &str, value: &str) -> Self {
+    Error::InvalidPropertyValue {
+      property: property.to_string(),
+      value: value.to_string(),
+    }
+  }
+}
+
+impl fmt::Display for Error {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      Error::InvalidProperty(str) => write!(f, "Invalid property: {}", str),
+      Error::InvalidParameter(str) => write!(f, "Invalid parameter: {}", str),
+      Error::InvalidParameters(str) => write!(f, "Invalid parameters: {}", str),
+      Error::UnclosedComponent(name) => write!(f, "Missing END:{}", name),
+      Error::UnexpectedEnd(name) => write!(f, "Unexpected END:{}", name),
+      Error::UnexpectedProperty(name) => write!(f, "Invalid property name: {}", name),
+      Error::InvalidPropertyValue { property, value } => {
+        write!(f, "Invalid {}: {}", property, value)
+      }
+      Error::InvalidValue { name, value } => write!(f, "Invalid {} value: {}", name, value),
+      Error::InvalidTimezone(tzid) => write!(f, "Invalid timezone: {}", tzid),
+      Error::ValueTypeMismatch(property) => {
+        write!(f, "{} value and value type do not match", property)
+      }
+      Error::MixedValueTypes(property) => write!(
+        f,
+        "All {} instances must have the same value type as specified in {}",
+        property, property
+      ),
+      Error::DtStartValueTypeMismatch(name) => {
+        write!(f, "{} value type does not match DTSTART value type", name)
+      }
+      Error::MissingProperty(name) => write!(f, "{} is required", name),
+      Error::DuplicateProperty(name) => write!(f, "Only one {} is allowed", name),
+      Error::MissingTimezone(property) => write!(f, "TZID is required for non-UTC {}", property),
+      Error::NonexistentLocalTime { datetime, tzid } => {
+        write!(f, "Local time {} does not exist in {}", datetime, tzid)
+      }
+      Error::AmbiguousLocalTime { datetime, tzid } => {
+        write!(f, "Local time {} is ambiguous in {}", datetime, tzid)
+      }
+      Error::Validation(message) => write!(f, "{}", message),
+      Error::InfiniteSet => write!(f, "Cannot count occurrences of an infinite set"),
+      Error::Located { error, location } => write!(
+        f,
+        "{} at line {}, col {}-{}",
+        error, location.line, location.start, location.end
+      ),
+    }
+  }
+}
+
+impl std::error::Error for Error {}
@@ -1,7 +1,12 @@
+pub mod calendar_component;
+pub mod diagnostic;
+pub mod error;
 pub mod exdate;
+pub mod extent;
 pub mod frequency;
 pub mod month;
 pub mod n_weekday;
+pub mod property;
 pub mod rdate;
 pub mod rrule;
 pub mod rrule_set;
@@ -0,0 +1,49 @@
+use super::rrule_set::RRuleSet;
+use crate::rrule::calendar_component;
+use napi_derive::napi;
+
+#[napi(js_name = "CalendarComponent")]
+pub struct CalendarComponent {
+  component: calendar_component::CalendarComponent,
+}
+
+#[napi]
+impl CalendarComponent {
+  #[napi(getter)]
+  pub fn name(&self) -> napi::Result<String> {
+    Ok(self.component.name().to_string())
+  }
+
+  #[napi(getter)]
+  pub fn uid(&self) -> napi::Result<Option<String>> {
+    Ok(self.component.uid().cloned())
+  }
+
+  #[napi(getter)]
+  pub fn summary(&self) -> napi::Result<Option<String>> {
+    Ok(self.component.summary().cloned())
+  }
+
+  #[napi(getter)]
+  pub fn components(&self) -> napi::Result<Vec<String>> {
+    Ok(
+      self
+        .component
+        .components()
+        .iter()
+        .map(|component| component.to_properties().to_string())
+        .collect(),
+    )
+  }
+
+  #[napi(getter, ts_return_type = "RRuleSet")]
+  pub fn rrule_set(&self) -> napi::Result<RRuleSet> {
+    Ok(self.component.rrule_set().clone().into())
+  }
+}
+
+impl From<calendar_component::CalendarComponent> for CalendarComponent {
+  fn from(component: calendar_component::CalendarComponent) -> Self {
+    Self { component }
+  }
+}
@@ -0,0 +1,20 @@
+use crate::rrule::diagnostic;
+use napi_derive::napi;
+
+#[napi(object, js_name = "Diagnostic")]
+pub struct Diagnostic {
+  #[napi(ts_type = "'error' | 'warning'")]
+  pub severity: String,
+  pub code: String,
+  pub message: String,
+}
+
+impl From<&diagnostic::Diagnostic> for Diagnostic {
+  fn from(diagnostic: &diagnostic::Diagnostic) -> Self {
+    Self {
+      severity: diagnostic.severity.to_string(),
+      code: diagnostic.code.to_string(),
+      message: diagnostic.message.clone(),
+    }
+  }
+}
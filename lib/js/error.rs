@@ -0,0 +1,108 @@
+use crate::error;
+use napi::bindgen_prelude::{JsObjectValue, Reference, SharedReference};
+use napi::Env;
+use napi_derive::napi;
+
+/// Status of the errors thrown into JS. It becomes the `code` of the thrown
+/// error, so errors can be told apart without matching their messages.
+pub struct ErrorCode(String);
+
+impl AsRef<str> for ErrorCode {
+  fn as_ref(&self) -> &str {
+    &self.0
+  }
+}
+
+pub type Result<T> = napi::Result<T, ErrorCode>;
+
+/// Position of the token which raised a parse error. Lines and columns are
+/// 1-based, `end` is the column of the last character of the token.
+#[napi(object, js_name = "ErrorLocation")]
+pub struct ErrorLocation {
+  pub line: u32,
+  pub start: u32,
+  pub end: u32,
+  pub token: String,
+}
+
+impl From<&error::Location> for ErrorLocation {
+  fn from(location: &error::Location) -> Self {
+    Self {
+      line: location.line as u32,
+      start: location.start as u32,
+      end: location.end as u32,
+      token: location.token.clone(),
+    }
+  }
+}
+
+impl From<error::Error> for napi::Error<ErrorCode> {
+  fn from(err: error::Error) -> Self {
+    napi::Error::new(ErrorCode(err.code().to_string()), err.to_string())
+  }
+}
+
+/// Same as `Reference::share_with`, but keeps the code of an error raised by
+/// `f` instead of reporting it as a generic failure.
+pub fn share_with<T: 'static, S: 'static>(
+  reference: Reference<T>,
+  env: Env,
+  f: impl FnOnce(&'static mut T) -> std::result::Result<S, error::Error>,
+) -> Result<SharedReference<T, S>> {
+  let mut error = None;
+  let shared = reference.share_with(env, |value| {
+    f(value).map_err(|err| {
+      let reason = err.to_string();
+
+      error = Some(err);
+
+      napi::Error::from_reason(reason)
+    })
+  });
+
+  match (shared, error) {
+    (_, Some(err)) => Err(err.into()),
+    (Ok(shared), None) => Ok(shared),
+    (Err(err), None) => Err(napi::Error::new(
+      ErrorCode(err.status.as_ref().to_string()),
+      err.reason.clone(),
+    )),
+  }
+}
+
+/// Throws `err` along with the location of the token which raised it, which
+/// becomes the `location` of the thrown error.
+pub fn throw_located(env: &Env, err: error::Error) -> napi::Error<ErrorCode> {
+  if err.location().is_none() {
+    return err.into();
+  }
+
+  match throw_error(env, &err) {
+    Ok(()) => napi::Error::from_status(ErrorCode(
+      napi::Status::PendingException.as_ref().to_string(),
+    )),
+    Err(_) => err.into(),
+  }
+}
+
+/// Same as `throw_located`, but for the methods which can only fail with a
+/// generic `napi::Error`, such as the ones returning `&Self`. The code of
+/// `err` is thrown along with it all the same.
+pub fn throw(env: &Env, err: error::Error) -> napi::Error {
+  match throw_error(env, &err) {
+    Ok(()) => napi::Error::from_status(napi::Status::PendingException),
+    Err(_) => napi::Error::from_reason(err.to_string()),
+  }
+}
+
+fn throw_error(env: &Env, err: &error::Error) -> napi::Result<()> {
+  let mut object = env.create_error(napi::Error::from_reason(err.to_string()))?;
+
+  object.set_named_property("code", err.code())?;
+
+  if let Some(location) = err.location() {
+    object.set_named_property("location", ErrorLocation::from(location))?;
+  }
+
+  env.throw(object)
+}
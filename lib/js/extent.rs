@@ -0,0 +1,24 @@
+use crate::rrule::extent;
+use napi::bindgen_prelude::Int32Array;
+use napi_derive::napi;
+
+#[napi(object, js_name = "Extent")]
+pub struct Extent {
+  pub finite: bool,
+  pub last: Option<Int32Array>,
+}
+
+impl From<&extent::Extent> for Extent {
+  fn from(extent: &extent::Extent) -> Self {
+    match extent {
+      extent::Extent::Infinite => Self {
+        finite: false,
+        last: None,
+      },
+      extent::Extent::Finite { last } => Self {
+        finite: true,
+        last: last.as_ref().map(|last| last.into()),
+      },
+    }
+  }
+}
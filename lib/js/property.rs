@@ -0,0 +1,128 @@
+use crate::{
+  error::Error,
+  serialization::{
+    parameters::Parameters,
+    property::{self, Value},
+  },
+};
+use napi_derive::napi;
+
+/// Parameter, or recurrence rule part, which is not interpreted but kept as
+/// parsed.
+#[napi(object, js_name = "Parameter")]
+pub struct Parameter {
+  pub name: String,
+  #[napi(ts_type = "readonly string[]")]
+  pub values: Vec<String>,
+}
+
+/// Property which is not interpreted but kept as parsed.
+#[napi(object, js_name = "Property")]
+pub struct Property {
+  pub name: String,
+  #[napi(ts_type = "readonly Parameter[]")]
+  pub parameters: Vec<Parameter>,
+  pub value: String,
+}
+
+pub fn from_parameters(parameters: &Parameters) -> Vec<Parameter> {
+  parameters
+    .iter()
+    .map(|(name, values)| Parameter {
+      name: name.clone(),
+      values: values.clone(),
+    })
+    .collect()
+}
+
+pub fn to_parameters(parameters: Vec<Parameter>) -> Result<Parameters, Error> {
+  let mut result = Parameters::new();
+
+  for parameter in parameters {
+    let valid = parameter.values.iter().all(|value| {
+      !value
+        .chars()
+        .any(|char| char.is_control() && !matches!(char, '\t' | '\n' | '\r'))
+    });
+
+    if !property::is_name(&parameter.name) || !valid {
+      return Err(Error::InvalidParameter(parameter.name));
+    }
+
+    result.insert_values(parameter.name, parameter.values);
+  }
+
+  Ok(result)
+}
+
+/// Same as `from_parameters`, but splits the comma separated values of the
+/// rule parts.
+pub fn from_parts(parts: &Parameters) -> Vec<Parameter> {
+  parts
+    .iter()
+    .map(|(name, values)| Parameter {
+      name: name.clone(),
+      values: values
+        .iter()
+        .flat_map(|value| value.split(','))
+        .map(|value| value.to_string())
+        .collect(),
+    })
+    .collect()
+}
+
+pub fn to_parts(parts: Vec<Parameter>) -> Result<Parameters, Error> {
+  let mut result = Parameters::new();
+
+  for part in parts {
+    let value = part.values.join(",");
+
+    if !property::is_name(&part.name)
+      || value.contains([';', '='])
+      || value.chars().any(char::is_control)
+    {
+      return Err(Error::invalid_value(&part.name, &value));
+    }
+
+    result.insert(part.name, value);
+  }
+
+  Ok(result)
+}
+
+impl From<&property::Property> for Property {
+  fn from(property: &property::Property) -> Self {
+    Self {
+      name: property.name().to_string(),
+      parameters: from_parameters(property.parameters()),
+      value: property.value().to_string(),
+    }
+  }
+}
+
+impl TryFrom<Property> for property::Property {
+  type Error = Error;
+
+  fn try_from(property: Property) -> Result<Self, Self::Error> {
+    if !property::is_name(&property.name) {
+      return Err(Error::InvalidProperty(property.name));
+    }
+
+    if property
+      .value
+      .chars()
+      .any(|char| char.is_control() && char != '\t')
+    {
+      return Err(Error::invalid_property_value(
+        &property.name,
+        &property.value,
+      ));
+    }
+
+    Ok(property::Property::new(
+      property.name.to_uppercase(),
+      to_parameters(property.parameters)?,
+      Value::Single(property.value),
+    ))
+  }
+}
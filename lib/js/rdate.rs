@@ -1,6 +1,9 @@
+use super::error::Result;
+use super::property::{self, Parameter};
 use crate::rrule::{
-  datetime::{self},
+  datetime::{self, NO_OFFSET},
   rdate,
+  timezone::Timezones,
 };
 use napi::bindgen_prelude::Int32Array;
 use napi_derive::napi;
@@ -13,15 +16,16 @@ pub struct RDate {
 #[napi]
 impl RDate {
   #[napi(constructor)]
-  pub fn new(dates: Int32Array, tzid: Option<String>) -> napi::Result<Self> {
-    let tzid: Option<chrono_tz::Tz> = match tzid {
-      Some(tzid) => Some(
-        tzid
-          .parse()
-          .map_err(|e| napi::Error::new(napi::Status::GenericFailure, e))?,
-      ),
-      None => None,
-    };
+  pub fn new(
+    dates: Int32Array,
+    tzid: Option<String>,
+    #[napi(ts_arg_type = "(readonly Parameter[]) | undefined | null")] unknown_parameters: Option<
+      Vec<Parameter>,
+    >,
+    #[napi(ts_arg_type = "(readonly string[]) | undefined | null")] timezones: Option<Vec<String>>,
+  ) -> Result<Self> {
+    let timezones = Timezones::from_definitions(&timezones.unwrap_or_default())?;
+    let tzid = tzid.map(|tzid| timezones.resolve(&tzid)).transpose()?;
 
     let mut datetimes = Vec::<datetime::DateTime>::new();
 
@@ -34,8 +38,9 @@ impl RDate {
       );
     }
 
-    let rdate = rdate::RDate::new(datetimes, tzid, None)
-      .map_err(|e| napi::Error::new(napi::Status::GenericFailure, e))?;
+    let rdate = rdate::RDate::new(datetimes, tzid, None)?.set_unknown_parameters(
+      property::to_parameters(unknown_parameters.unwrap_or_default())?,
+    );
 
     Ok(Self { rdate })
   }
@@ -53,12 +58,12 @@ impl RDate {
         arr.push(time.hour() as i32);
         arr.push(time.minute() as i32);
         arr.push(time.second() as i32);
-        arr.push(time.offset().unwrap_or(-1));
+        arr.push(time.offset().unwrap_or(NO_OFFSET));
       } else {
         arr.push(-1);
         arr.push(-1);
         arr.push(-1);
-        arr.push(-1);
+        arr.push(NO_OFFSET);
       }
     }
 
@@ -67,7 +72,12 @@ impl RDate {
 
   #[napi(getter)]
   pub fn tzid(&self) -> napi::Result<Option<String>> {
-    Ok(self.rdate.tzid().and_then(|tzid| Some(tzid.to_string())))
+    Ok(self.rdate.tzid().as_ref().map(|tzid| tzid.to_string()))
+  }
+
+  #[napi(getter)]
+  pub fn unknown_parameters(&self) -> napi::Result<Vec<Parameter>> {
+    Ok(property::from_parameters(self.rdate.unknown_parameters()))
   }
 }
 
@@ -1,6 +1,14 @@
-use super::{frequency::Frequency, month::Month, n_weekday::NWeekday, weekday::Weekday};
-use crate::rrule::{datetime, n_weekday, rrule};
-use napi::{bindgen_prelude::Int32Array, Either};
+use super::{
+  diagnostic::Diagnostic,
+  error::{self, Result},
+  frequency::Frequency,
+  month::Month,
+  n_weekday::NWeekday,
+  property::{self, Parameter},
+  weekday::Weekday,
+};
+use crate::rrule::{datetime, dtstart, n_weekday, rrule, timezone::Timezone};
+use napi::{bindgen_prelude::Int32Array, Either, Env};
 use napi_derive::napi;
 
 #[napi(js_name = "RRule")]
@@ -28,7 +36,13 @@ impl RRule {
     #[napi(ts_arg_type = "(readonly number[]) | undefined | null")] by_month: Option<Vec<Month>>,
     #[napi(ts_arg_type = "(readonly number[]) | undefined | null")] by_weekno: Option<Vec<i8>>,
     #[napi(ts_arg_type = "(readonly number[]) | undefined | null")] by_yearday: Option<Vec<i16>>,
-  ) -> napi::Result<Self> {
+    #[napi(ts_arg_type = "(readonly Parameter[]) | undefined | null")] unknown_parameters: Option<
+      Vec<Parameter>,
+    >,
+    #[napi(ts_arg_type = "(readonly Parameter[]) | undefined | null")] unknown_parts: Option<
+      Vec<Parameter>,
+    >,
+  ) -> Result<Self> {
     let mut rrule = rrule::RRule::new(frequency.into());
     rrule = rrule.set_interval(interval);
     rrule = rrule.set_count(count);
@@ -61,14 +75,17 @@ impl RRule {
         })
         .collect(),
     );
+    rrule = rrule.set_unknown_parameters(property::to_parameters(
+      unknown_parameters.unwrap_or_default(),
+    )?);
+    rrule = rrule.set_unknown_parts(property::to_parts(unknown_parts.unwrap_or_default())?);
 
     Ok(Self { rrule })
   }
 
   #[napi(factory, ts_return_type = "RRule")]
-  pub fn parse(str: String) -> napi::Result<Self> {
-    let rrule = rrule::RRule::from_str(&str)
-      .map_err(|e| napi::Error::new(napi::Status::GenericFailure, e))?;
+  pub fn parse(env: Env, str: String) -> Result<Self> {
+    let rrule = rrule::RRule::from_str(&str).map_err(|err| error::throw_located(&env, err))?;
 
     Ok(Self { rrule })
   }
@@ -100,6 +117,16 @@ impl RRule {
     )
   }
 
+  #[napi(getter)]
+  pub fn unknown_parameters(&self) -> napi::Result<Vec<Parameter>> {
+    Ok(property::from_parameters(self.rrule.unknown_parameters()))
+  }
+
+  #[napi(getter)]
+  pub fn unknown_parts(&self) -> napi::Result<Vec<Parameter>> {
+    Ok(property::from_parts(self.rrule.unknown_parts()))
+  }
+
   #[napi(getter)]
   pub fn by_hour(&self) -> napi::Result<Vec<u8>> {
     Ok(self.rrule.by_hour().clone())
@@ -157,6 +184,36 @@ impl RRule {
     Ok(self.rrule.until().map(|datetime| datetime.into()))
   }
 
+  #[napi]
+  pub fn validate(&self) -> napi::Result<Vec<Diagnostic>> {
+    Ok(
+      self
+        .rrule
+        .validate()
+        .iter()
+        .map(|diagnostic| diagnostic.into())
+        .collect(),
+    )
+  }
+
+  #[napi]
+  pub fn lint(&self, dtstart: Option<Int32Array>, tzid: Option<String>) -> Result<Vec<Diagnostic>> {
+    let tzid: Option<Timezone> = tzid.map(|tzid| tzid.parse()).transpose()?;
+
+    let dtstart = dtstart
+      .map(|dtstart| dtstart::DtStart::new(dtstart.into(), tzid, None))
+      .transpose()?;
+
+    Ok(
+      self
+        .rrule
+        .lint(dtstart.as_ref())
+        .iter()
+        .map(|diagnostic| diagnostic.into())
+        .collect(),
+    )
+  }
+
   #[napi]
   pub fn to_string(&self) -> napi::Result<String> {
     Ok(self.rrule.to_string())
@@ -1,16 +1,22 @@
-use std::iter::Skip;
-
+use super::calendar_component::CalendarComponent;
+use super::error::{self, Result};
 use super::exdate::ExDate;
+use super::extent::Extent;
+use super::property::{self, Parameter, Property};
 use super::rdate::RDate;
 use super::rrule::RRule;
-use crate::rrule::datetime::DateTime;
+use crate::error::Error;
+use crate::rrule::datetime::{DateTime, NO_OFFSET};
+use crate::rrule::disambiguation::Disambiguation;
 use crate::rrule::dtstart::DtStart;
+use crate::rrule::preserve::Preserve;
+use crate::rrule::timezone::{Timezone, Timezones};
 use crate::rrule::value_type::ValueType;
-use crate::rrule::{exdate, rdate, rrule, rrule_set};
+use crate::rrule::{calendar_component, exdate, rdate, rrule, rrule_set};
+use crate::serialization::{component::Component, properties::Properties};
 #[cfg(not(target_family = "wasm"))]
-use napi::bindgen_prelude::{Int32Array, Int32ArraySlice, Reference, SharedReference};
-#[cfg(target_family = "wasm")]
-use napi::bindgen_prelude::{Int32Array, Reference, SharedReference};
+use napi::bindgen_prelude::Int32ArraySlice;
+use napi::bindgen_prelude::{Float64Array, Int32Array, Reference, SharedReference};
 use napi::Env;
 use napi_derive::napi;
 use replace_with::replace_with_or_abort_and_return;
@@ -23,6 +29,7 @@ pub struct RRuleSet {
 #[napi]
 impl RRuleSet {
   #[napi(constructor)]
+  #[allow(clippy::too_many_arguments)]
   pub fn new(
     dtstart: Int32Array,
     tzid: Option<String>,
@@ -31,23 +38,25 @@ impl RRuleSet {
     #[napi(ts_arg_type = "(readonly RRule[]) | undefined | null")] exrules: Option<Vec<&RRule>>,
     #[napi(ts_arg_type = "(readonly ExDate[]) | undefined | null")] exdates: Option<Vec<&ExDate>>,
     #[napi(ts_arg_type = "(readonly RDate[]) | undefined | null")] rdates: Option<Vec<&RDate>>,
-  ) -> napi::Result<Self> {
-    let tzid: Option<chrono_tz::Tz> = match tzid {
-      Some(tzid) => Some(
-        tzid
-          .parse()
-          .map_err(|e| napi::Error::new(napi::Status::GenericFailure, e))?,
-      ),
-      None => None,
-    };
+    #[napi(ts_arg_type = "(readonly Parameter[]) | undefined | null")]
+    dtstart_unknown_parameters: Option<Vec<Parameter>>,
+    #[napi(ts_arg_type = "(readonly Property[]) | undefined | null")] unknown_properties: Option<
+      Vec<Property>,
+    >,
+    #[napi(ts_arg_type = "(readonly string[]) | undefined | null")] timezones: Option<Vec<String>>,
+    #[napi(ts_arg_type = "'reject' | 'earlier' | 'later' | 'shift-forward' | undefined | null")]
+    disambiguation: Option<String>,
+  ) -> Result<Self> {
+    let timezones = Timezones::from_definitions(&timezones.unwrap_or_default())?;
+    let tzid = tzid.map(|tzid| timezones.resolve(&tzid)).transpose()?;
 
     let dtstat_value = dtstart_value
       .map(|value| value.parse::<ValueType>())
-      .transpose()
-      .map_err(|e| napi::Error::new(napi::Status::GenericFailure, e))?;
+      .transpose()?;
 
-    let dtstart = DtStart::new(dtstart.into(), tzid, dtstat_value)
-      .map_err(|e| napi::Error::new(napi::Status::GenericFailure, e))?;
+    let dtstart = DtStart::new(dtstart.into(), tzid, dtstat_value)?.set_unknown_parameters(
+      property::to_parameters(dtstart_unknown_parameters.unwrap_or_default())?,
+    );
 
     let rrules: Vec<rrule::RRule> = rrules
       .unwrap_or_default()
@@ -73,15 +82,24 @@ impl RRuleSet {
       .map(Into::into)
       .collect();
 
+    let disambiguation = disambiguation
+      .map(|disambiguation| disambiguation.parse::<Disambiguation>())
+      .transpose()?
+      .unwrap_or_default();
+
     let rrule_set = rrule_set::RRuleSet::new(dtstart)
-      .set_rrules(rrules)
-      .map_err(|e| napi::Error::new(napi::Status::GenericFailure, e))?
-      .set_exrules(exrules)
-      .map_err(|e| napi::Error::new(napi::Status::GenericFailure, e))?
-      .set_exdates(exdates)
-      .map_err(|e| napi::Error::new(napi::Status::GenericFailure, e))?
-      .set_rdates(rdates)
-      .map_err(|e| napi::Error::new(napi::Status::GenericFailure, e))?;
+      .set_disambiguation(disambiguation)
+      .set_rrules(rrules)?
+      .set_exrules(exrules)?
+      .set_exdates(exdates)?
+      .set_rdates(rdates)?
+      .set_unknown_properties(
+        unknown_properties
+          .unwrap_or_default()
+          .into_iter()
+          .map(TryInto::try_into)
+          .collect::<std::result::Result<_, Error>>()?,
+      );
 
     Ok(Self { rrule_set })
   }
@@ -96,6 +114,45 @@ impl RRuleSet {
     Ok(self.rrule_set.dtstart().value().into())
   }
 
+  #[napi(getter)]
+  pub fn dtstart_unknown_parameters(&self) -> napi::Result<Vec<Parameter>> {
+    Ok(property::from_parameters(
+      self.rrule_set.dtstart().unknown_parameters(),
+    ))
+  }
+
+  #[napi(getter)]
+  pub fn unknown_properties(&self) -> napi::Result<Vec<Property>> {
+    Ok(
+      self
+        .rrule_set
+        .unknown_properties()
+        .iter()
+        .map(Into::into)
+        .collect(),
+    )
+  }
+
+  #[napi(getter)]
+  pub fn timezones(&self) -> napi::Result<Vec<String>> {
+    Ok(
+      self
+        .rrule_set
+        .custom_timezones()
+        .iter()
+        .map(|timezone| timezone.component().to_properties().to_string())
+        .collect(),
+    )
+  }
+
+  #[napi(
+    getter,
+    ts_return_type = "'reject' | 'earlier' | 'later' | 'shift-forward'"
+  )]
+  pub fn disambiguation(&self) -> napi::Result<String> {
+    Ok(self.rrule_set.disambiguation().to_string())
+  }
+
   #[napi(getter, ts_return_type = "RRule[]")]
   pub fn rrules(&self) -> napi::Result<Vec<RRule>> {
     Ok(
@@ -145,132 +202,183 @@ impl RRuleSet {
   }
 
   #[napi(factory, ts_return_type = "RRuleSet")]
-  pub fn parse(str: String) -> napi::Result<Self> {
-    let rrule_set: rrule_set::RRuleSet = str
-      .parse()
-      .map_err(|e| napi::Error::new(napi::Status::GenericFailure, e))?;
+  pub fn parse(env: Env, str: String) -> Result<Self> {
+    let rrule_set: rrule_set::RRuleSet =
+      str.parse().map_err(|err| error::throw_located(&env, err))?;
 
     Ok(Self { rrule_set })
   }
 
+  #[napi(ts_return_type = "CalendarComponent[]")]
+  pub fn parse_calendar(env: Env, str: String) -> Result<Vec<CalendarComponent>> {
+    let components = calendar_component::CalendarComponent::parse_all(&str)
+      .map_err(|err| error::throw_located(&env, err))?;
+
+    Ok(components.into_iter().map(Into::into).collect())
+  }
+
+  #[napi]
+  pub fn all(&self, limit: Option<i32>) -> Result<Int32Array> {
+    Ok(to_flat_int32_array(self.rrule_set.iterator()?, limit))
+  }
+
   #[napi]
-  pub fn all(&self, limit: Option<i32>) -> napi::Result<Int32Array> {
-    let mut arr = match limit {
-      Some(l) => Vec::<i32>::with_capacity((l as usize) * 7),
-      None => Vec::<i32>::with_capacity(700),
-    };
+  pub fn all_in_timezone(&self, tzid: String, limit: Option<i32>) -> Result<Int32Array> {
+    let timezone: Timezone = tzid.parse()?;
+
+    Ok(to_flat_int32_array(
+      self.rrule_set.iterator_in(&timezone)?,
+      limit,
+    ))
+  }
 
-    let iter = self
+  #[napi]
+  pub fn between(
+    &self,
+    after_datetime: Int32Array,
+    before_datetime: Int32Array,
+    inclusive: Option<bool>,
+  ) -> Result<Int32Array> {
+    let occurrences = self.rrule_set.between(
+      &after_datetime.into(),
+      &before_datetime.into(),
+      inclusive.unwrap_or(false),
+    )?;
+
+    Ok(to_flat_int32_array(occurrences, None))
+  }
+
+  #[napi]
+  pub fn between_in_timezone(
+    &self,
+    tzid: String,
+    after_datetime: Int32Array,
+    before_datetime: Int32Array,
+    inclusive: Option<bool>,
+  ) -> Result<Int32Array> {
+    let timezone: Timezone = tzid.parse()?;
+    let occurrences = self.rrule_set.between_in(
+      &after_datetime.into(),
+      &before_datetime.into(),
+      inclusive.unwrap_or(false),
+      &timezone,
+    )?;
+
+    Ok(to_flat_int32_array(occurrences, None))
+  }
+
+  #[napi]
+  pub fn all_timestamps(&self, limit: Option<i32>) -> Result<Float64Array> {
+    let limit = limit.map_or(usize::MAX, |limit| limit.max(0) as usize);
+    let timestamps = self.rrule_set.timestamps()?.take(limit);
+
+    Ok(Float64Array::new(
+      timestamps.map(|timestamp| timestamp as f64).collect(),
+    ))
+  }
+
+  #[napi]
+  pub fn between_timestamps(
+    &self,
+    after_datetime: Int32Array,
+    before_datetime: Int32Array,
+    inclusive: Option<bool>,
+  ) -> Result<Float64Array> {
+    let timestamps = self.rrule_set.between_timestamps(
+      &after_datetime.into(),
+      &before_datetime.into(),
+      inclusive.unwrap_or(false),
+    )?;
+
+    Ok(Float64Array::new(
+      timestamps
+        .into_iter()
+        .map(|timestamp| timestamp as f64)
+        .collect(),
+    ))
+  }
+
+  #[napi]
+  pub fn after(&self, datetime: Int32Array, inclusive: Option<bool>) -> Result<Option<Int32Array>> {
+    let occurrence = self
       .rrule_set
-      .iterator()
-      .map_err(|e| napi::Error::new(napi::Status::GenericFailure, e))?;
+      .after(&datetime.into(), inclusive.unwrap_or(false))?;
 
-    for (index, datetime) in iter.enumerate() {
-      if let Some(limit) = limit {
-        if index as i32 >= limit {
-          break;
-        }
-      }
+    Ok(occurrence.map(|datetime| (&datetime).into()))
+  }
 
-      arr.push(datetime.year() as i32);
-      arr.push(datetime.month() as i32);
-      arr.push(datetime.day() as i32);
-
-      if let Some(time) = datetime.time() {
-        arr.push(time.hour() as i32);
-        arr.push(time.minute() as i32);
-        arr.push(time.second() as i32);
-        arr.push(time.offset().unwrap_or(-1));
-      } else {
-        arr.push(-1);
-        arr.push(-1);
-        arr.push(-1);
-        arr.push(-1);
-      }
-    }
+  #[napi]
+  pub fn before(
+    &self,
+    datetime: Int32Array,
+    inclusive: Option<bool>,
+  ) -> Result<Option<Int32Array>> {
+    let occurrence = self
+      .rrule_set
+      .before(&datetime.into(), inclusive.unwrap_or(false))?;
 
-    Ok(Int32Array::new(arr))
+    Ok(occurrence.map(|datetime| (&datetime).into()))
   }
 
-  fn is_after(&self, timestamp: i64, after_timestamp: i64, inclusive: Option<bool>) -> bool {
-    let is_inclusive = inclusive.unwrap_or(false);
+  #[napi]
+  pub fn includes(&self, datetime: Int32Array) -> Result<bool> {
+    self
+      .rrule_set
+      .includes(&datetime.into())
+      .map_err(Into::into)
+  }
 
-    if is_inclusive {
-      timestamp >= after_timestamp
-    } else {
-      timestamp > after_timestamp
-    }
+  #[napi]
+  pub fn count(&self) -> Result<f64> {
+    self
+      .rrule_set
+      .count()
+      .map(|count| count as f64)
+      .map_err(Into::into)
   }
 
-  fn is_before(&self, timestamp: i64, before_timestamp: i64, inclusive: Option<bool>) -> bool {
-    let is_inclusive = inclusive.unwrap_or(false);
+  #[napi]
+  pub fn extent(&self) -> Result<Extent> {
+    let extent = self.rrule_set.extent()?;
 
-    if is_inclusive {
-      timestamp <= before_timestamp
-    } else {
-      timestamp < before_timestamp
-    }
+    Ok((&extent).into())
   }
 
   #[napi]
-  pub fn between(
+  pub fn count_between(
     &self,
     after_datetime: Int32Array,
     before_datetime: Int32Array,
     inclusive: Option<bool>,
-  ) -> napi::Result<Int32Array> {
-    let mut arr = Vec::<i32>::new();
-
-    let timezone = self.rrule_set.dtstart().derive_timezone();
-    let after_timestamp = DateTime::from(after_datetime)
-      .to_datetime(&timezone)
-      .map_err(|e| napi::Error::new(napi::Status::GenericFailure, e))?
-      .timestamp_millis();
-    let before_timestamp = DateTime::from(before_datetime)
-      .to_datetime(&timezone)
-      .map_err(|e| napi::Error::new(napi::Status::GenericFailure, e))?
-      .timestamp_millis();
-
-    let iterator = self
+  ) -> Result<f64> {
+    self
       .rrule_set
-      .iterator()
-      .map_err(|e| napi::Error::new(napi::Status::GenericFailure, e))?;
-
-    for date in iterator {
-      let date_timestamp = date
-        .to_datetime(&timezone)
-        .map_err(|e| napi::Error::new(napi::Status::GenericFailure, e))?
-        .timestamp_millis();
-      let is_after = self.is_after(date_timestamp, after_timestamp, inclusive);
-      let is_before = self.is_before(date_timestamp, before_timestamp, inclusive);
-
-      if is_after && is_before {
-        arr.push(date.year() as i32);
-        arr.push(date.month() as i32);
-        arr.push(date.day() as i32);
-
-        if let Some(time) = date.time() {
-          arr.push(time.hour() as i32);
-          arr.push(time.minute() as i32);
-          arr.push(time.second() as i32);
-          arr.push(time.offset().unwrap_or(-1));
-        } else {
-          arr.push(-1);
-          arr.push(-1);
-          arr.push(-1);
-          arr.push(-1);
-        }
-      } else if !is_before {
-        break;
-      }
-    }
+      .count_between(
+        &after_datetime.into(),
+        &before_datetime.into(),
+        inclusive.unwrap_or(false),
+      )
+      .map(|count| count as f64)
+      .map_err(Into::into)
+  }
+
+  #[napi]
+  pub fn nth(&self, index: u32) -> Result<Option<Int32Array>> {
+    let occurrence = self.rrule_set.nth(index as usize)?;
+
+    Ok(occurrence.map(|datetime| (&datetime).into()))
+  }
 
-    Ok(Int32Array::new(arr))
+  #[napi]
+  pub fn index_of(&self, datetime: Int32Array) -> Result<Option<f64>> {
+    let index = self.rrule_set.index_of(&datetime.into())?;
+
+    Ok(index.map(|index| index as f64))
   }
 
   #[napi]
-  pub fn set_from_string(&mut self, str: String) -> napi::Result<&Self> {
-    replace_with_or_abort_and_return(&mut self.rrule_set, |set| {
+  pub fn set_from_string(&mut self, env: Env, str: String) -> napi::Result<&Self> {
+    let error = replace_with_or_abort_and_return(&mut self.rrule_set, |set| {
       let new_set = set.clone();
 
       match new_set.set_from_string(&str) {
@@ -279,12 +387,70 @@ impl RRuleSet {
       }
     });
 
-    Ok(self)
+    match error {
+      Some(err) => Err(error::throw(&env, err)),
+      None => Ok(self),
+    }
   }
 
   #[napi]
-  pub fn to_string(&self) -> napi::Result<String> {
-    Ok(self.rrule_set.to_string())
+  pub fn to_string(&self, fold: Option<bool>) -> napi::Result<String> {
+    match fold.unwrap_or(false) {
+      true => Ok(self.rrule_set.to_folded_string()),
+      false => Ok(self.rrule_set.to_string()),
+    }
+  }
+
+  #[napi]
+  pub fn to_calendar(
+    &self,
+    uid: Option<String>,
+    summary: Option<String>,
+    dtend: Option<Int32Array>,
+    duration: Option<String>,
+    dtstamp: Option<Int32Array>,
+    #[napi(ts_arg_type = "(readonly string[]) | undefined | null")] components: Option<Vec<String>>,
+  ) -> Result<String> {
+    let mut nested = Vec::new();
+
+    for component in components.unwrap_or_default() {
+      let properties: Properties = component.parse()?;
+
+      nested.extend(Component::from_properties(properties)?);
+    }
+
+    let component = calendar_component::CalendarComponent::new(self.rrule_set.clone())
+      .set_uid(uid)
+      .set_summary(summary)
+      .set_dtend(dtend.map(DateTime::from))?
+      .set_duration(duration)?
+      .set_dtstamp(dtstamp.map(DateTime::from))?
+      .set_components(nested);
+
+    Ok(component.to_ics())
+  }
+
+  #[napi(ts_return_type = "RRuleSet")]
+  pub fn resolve_floating(&self, tzid: String) -> Result<Self> {
+    let timezone: Timezone = tzid.parse()?;
+
+    Ok(Self {
+      rrule_set: self.rrule_set.clone().resolve_floating(&timezone)?,
+    })
+  }
+
+  #[napi(ts_return_type = "RRuleSet")]
+  pub fn with_timezone(
+    &self,
+    tzid: String,
+    #[napi(ts_arg_type = "'instant' | 'wall-clock'")] preserve: String,
+  ) -> Result<Self> {
+    let timezone: Timezone = tzid.parse()?;
+    let preserve: Preserve = preserve.parse()?;
+
+    Ok(Self {
+      rrule_set: self.rrule_set.clone().with_timezone(&timezone, preserve)?,
+    })
   }
 
   #[napi]
@@ -293,22 +459,144 @@ impl RRuleSet {
     this: Reference<RRuleSet>,
     env: Env,
     skip: Option<i32>,
-  ) -> napi::Result<RRuleSetIterator> {
-    let iterator = this.share_with(env, |set: &mut RRuleSet| {
+  ) -> Result<RRuleSetIterator> {
+    let iterator = error::share_with(this, env, |set: &mut RRuleSet| {
+      let mut iter = set.rrule_set.iterator()?;
+
+      if let Some(skip) = skip.filter(|skip| *skip > 0) {
+        iter.nth(skip as usize - 1);
+      }
+
+      Ok(Box::new(iter.map(Ok)) as Occurrences)
+    })?;
+
+    Ok(RRuleSetIterator { iterator })
+  }
+
+  #[napi]
+  pub fn iterator_in_timezone(
+    &self,
+    this: Reference<RRuleSet>,
+    env: Env,
+    tzid: String,
+  ) -> Result<RRuleSetIterator> {
+    let timezone: Timezone = tzid.parse()?;
+    let iterator = error::share_with(this, env, |set: &mut RRuleSet| {
       set
         .rrule_set
-        .iterator()
-        .map(|iter| iter.skip(skip.unwrap_or(0) as usize))
-        .map_err(|e| napi::Error::new(napi::Status::GenericFailure, e))
+        .iterator_in(&timezone)
+        .map(|iter| Box::new(iter.map(Ok)) as Occurrences)
+    })?;
+
+    Ok(RRuleSetIterator { iterator })
+  }
+
+  #[napi]
+  pub fn timestamp_iterator(
+    &self,
+    this: Reference<RRuleSet>,
+    env: Env,
+  ) -> Result<RRuleSetTimestampIterator> {
+    let iterator = error::share_with(this, env, |set: &mut RRuleSet| {
+      set
+        .rrule_set
+        .timestamps()
+        .map(|iter| Box::new(iter) as Box<dyn Iterator<Item = i64>>)
+    })?;
+
+    Ok(RRuleSetTimestampIterator { iterator })
+  }
+
+  #[napi]
+  pub fn iterator_from(
+    &self,
+    this: Reference<RRuleSet>,
+    env: Env,
+    datetime: Int32Array,
+    inclusive: Option<bool>,
+  ) -> Result<RRuleSetIterator> {
+    let datetime: DateTime = datetime.into();
+    let iterator = error::share_with(this, env, |set: &mut RRuleSet| {
+      set
+        .rrule_set
+        .iterator_from(&datetime, inclusive.unwrap_or(false))
+        .map(|iter| Box::new(iter.map(Ok)) as Occurrences)
+    })?;
+
+    Ok(RRuleSetIterator { iterator })
+  }
+
+  #[napi]
+  pub fn reverse_iterator_from(
+    &self,
+    this: Reference<RRuleSet>,
+    env: Env,
+    datetime: Int32Array,
+    inclusive: Option<bool>,
+  ) -> Result<RRuleSetIterator> {
+    let datetime: DateTime = datetime.into();
+    let iterator = error::share_with(this, env, |set: &mut RRuleSet| {
+      set
+        .rrule_set
+        .reverse_iterator_from(&datetime, inclusive.unwrap_or(false))
+        .map(|iter| Box::new(iter) as Occurrences)
     })?;
 
     Ok(RRuleSetIterator { iterator })
   }
 }
 
+/// Packs up to `limit` occurrences into 7 ints each: year, month, day, hour,
+/// minute, second and offset, with -1 for the missing time and `NO_OFFSET`
+/// for the missing offset.
+fn to_flat_int32_array(
+  occurrences: impl IntoIterator<Item = DateTime>,
+  limit: Option<i32>,
+) -> Int32Array {
+  let mut arr = match limit {
+    Some(l) => Vec::<i32>::with_capacity((l as usize) * 7),
+    None => Vec::<i32>::with_capacity(700),
+  };
+
+  for (index, datetime) in occurrences.into_iter().enumerate() {
+    if let Some(limit) = limit {
+      if index as i32 >= limit {
+        break;
+      }
+    }
+
+    arr.push(datetime.year() as i32);
+    arr.push(datetime.month() as i32);
+    arr.push(datetime.day() as i32);
+
+    if let Some(time) = datetime.time() {
+      arr.push(time.hour() as i32);
+      arr.push(time.minute() as i32);
+      arr.push(time.second() as i32);
+      arr.push(time.offset().unwrap_or(NO_OFFSET));
+    } else {
+      arr.push(-1);
+      arr.push(-1);
+      arr.push(-1);
+      arr.push(NO_OFFSET);
+    }
+  }
+
+  Int32Array::new(arr)
+}
+
+impl From<rrule_set::RRuleSet> for RRuleSet {
+  fn from(rrule_set: rrule_set::RRuleSet) -> Self {
+    Self { rrule_set }
+  }
+}
+
+/// Occurrences iterated from JS, along with the errors raised while iterating.
+type Occurrences = Box<dyn Iterator<Item = std::result::Result<DateTime, Error>>>;
+
 #[napi]
 pub struct RRuleSetIterator {
-  iterator: SharedReference<RRuleSet, Skip<rrule_set::RRuleSetIterator>>,
+  iterator: SharedReference<RRuleSet, Occurrences>,
 }
 
 #[napi]
@@ -316,10 +604,10 @@ impl RRuleSetIterator {
   #[napi(ts_return_type = "boolean | Int32Array | null")]
   #[allow(clippy::should_implement_trait)]
   #[cfg(not(target_family = "wasm"))]
-  pub fn next(&mut self, mut store: Int32ArraySlice<'_>) -> bool {
-    let next = self.iterator.next();
+  pub fn next(&mut self, mut store: Int32ArraySlice<'_>) -> Result<bool> {
+    let next = self.iterator.next().transpose()?;
 
-    match next {
+    Ok(match next {
       Some(dt) => unsafe {
         let data: &mut [i32] = store.as_mut();
 
@@ -332,29 +620,46 @@ impl RRuleSetIterator {
           data[3] = time.hour() as i32;
           data[4] = time.minute() as i32;
           data[5] = time.second() as i32;
-          data[6] = time.offset().unwrap_or(-1);
+          data[6] = time.offset().unwrap_or(NO_OFFSET);
         } else {
           data[3] = -1;
           data[4] = -1;
           data[5] = -1;
-          data[6] = -1;
+          data[6] = NO_OFFSET;
         }
 
         true
       },
       None => false,
-    }
+    })
   }
 
   #[napi]
   #[allow(clippy::should_implement_trait)]
   #[cfg(target_family = "wasm")]
-  pub fn next(&mut self) -> Option<Int32Array> {
-    let next = self.iterator.next();
+  pub fn next(&mut self) -> Result<Option<Int32Array>> {
+    let next = self.iterator.next().transpose()?;
 
-    match next {
-      Some(dt) => Some((&dt).into()),
-      None => None,
-    }
+    Ok(next.map(|dt| (&dt).into()))
+  }
+}
+
+#[napi]
+pub struct RRuleSetTimestampIterator {
+  iterator: SharedReference<RRuleSet, Box<dyn Iterator<Item = i64>>>,
+}
+
+#[napi]
+impl RRuleSetTimestampIterator {
+  #[napi]
+  pub fn next(&mut self, size: u32) -> Float64Array {
+    Float64Array::new(
+      self
+        .iterator
+        .by_ref()
+        .take(size as usize)
+        .map(|timestamp| timestamp as f64)
+        .collect(),
+    )
   }
 }
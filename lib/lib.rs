@@ -1,3 +1,4 @@
+mod error;
 pub mod js;
 mod rrule;
 mod serialization;
@@ -1,15 +1,23 @@
 mod calendar;
+pub mod calendar_component;
 
 pub mod datetime;
+pub mod diagnostic;
+pub mod disambiguation;
 pub mod dtstart;
 pub mod exdate;
+pub mod extent;
 pub mod frequency;
+mod lint;
 pub mod month;
 pub mod n_weekday;
+pub mod preserve;
 pub mod rdate;
 #[allow(clippy::module_inception)]
 pub mod rrule;
 pub mod rrule_set;
 pub mod time;
+pub mod timezone;
+mod validation;
 pub mod value_type;
 pub mod weekday;
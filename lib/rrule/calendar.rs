@@ -1,7 +1,10 @@
-use crate::serialization::properties::Properties;
+use crate::{
+  error::Error,
+  serialization::{component::Component, properties::Properties, property::Property},
+};
 use std::str::FromStr;
 
-use super::{dtstart::DtStart, exdate::ExDate, rdate::RDate, rrule::RRule};
+use super::{dtstart::DtStart, exdate::ExDate, rdate::RDate, rrule::RRule, timezone::Timezones};
 
 pub struct Calendar {
   dtstarts: Vec<DtStart>,
@@ -9,6 +12,7 @@ pub struct Calendar {
   exrules: Vec<RRule>,
   exdates: Vec<ExDate>,
   rdates: Vec<RDate>,
+  unknown_properties: Vec<Property>,
 }
 
 impl From<Calendar>
@@ -18,6 +22,7 @@ impl From<Calendar>
     Vec<RRule>,
     Vec<ExDate>,
     Vec<RDate>,
+    Vec<Property>,
   )
 {
   fn from(val: Calendar) -> Self {
@@ -27,43 +32,40 @@ impl From<Calendar>
       val.exrules,
       val.exdates,
       val.rdates,
+      val.unknown_properties,
     )
   }
 }
 
-impl FromStr for Calendar {
-  type Err = String;
-
-  fn from_str(str: &str) -> Result<Self, Self::Err> {
-    let properties: Properties = str.parse().map_err(|e| format!("{}", e))?;
-
+impl Calendar {
+  /// Groups `properties` by name, resolving TZIDs through `timezones`.
+  pub fn from_properties(
+    properties: impl IntoIterator<Item = Property>,
+    timezones: &Timezones,
+  ) -> Result<Self, Error> {
     let mut dtstarts: Vec<DtStart> = Vec::new();
     let mut rrules: Vec<RRule> = Vec::new();
     let mut exrules: Vec<RRule> = Vec::new();
     let mut exdates: Vec<ExDate> = Vec::new();
     let mut rdates: Vec<RDate> = Vec::new();
+    let mut unknown_properties: Vec<Property> = Vec::new();
 
     for property in properties {
-      match property.name() {
+      let result = match property.name() {
         "DTSTART" => {
-          dtstarts.push(property.try_into()?);
-        }
-        "RRULE" => {
-          rrules.push(property.try_into()?);
-        }
-        "EXRULE" => {
-          exrules.push(property.try_into()?);
-        }
-        "EXDATE" => {
-          exdates.push(ExDate::from_property(property)?);
-        }
-        "RDATE" => {
-          rdates.push(RDate::from_property(property)?);
+          DtStart::from_property(&property, timezones).map(|dtstart| dtstarts.push(dtstart))
         }
+        "RRULE" => RRule::from_property(&property).map(|rrule| rrules.push(rrule)),
+        "EXRULE" => RRule::from_property(&property).map(|exrule| exrules.push(exrule)),
+        "EXDATE" => ExDate::from_property(&property, timezones).map(|exdate| exdates.push(exdate)),
+        "RDATE" => RDate::from_property(&property, timezones).map(|rdate| rdates.push(rdate)),
         _ => {
-          // Ignore unsupported properties
+          unknown_properties.push(property);
+          continue;
         }
-      }
+      };
+
+      result.map_err(|err| property.locate(err))?;
     }
 
     Ok(Calendar {
@@ -72,6 +74,46 @@ impl FromStr for Calendar {
       exrules,
       exdates,
       rdates,
+      unknown_properties,
     })
   }
 }
+
+/// Parses the properties of a recurrence set, resolving TZIDs through the
+/// VTIMEZONE components given along with them.
+impl FromStr for Calendar {
+  type Err = Error;
+
+  fn from_str(str: &str) -> Result<Self, Self::Err> {
+    let properties: Properties = str.parse()?;
+    let mut timezone_properties = Properties::new();
+    let mut other_properties = Vec::new();
+    // Depth of the VTIMEZONE component the properties are nested in
+    let mut depth = 0;
+
+    for property in properties {
+      let timezone = property
+        .value()
+        .to_string()
+        .eq_ignore_ascii_case("VTIMEZONE");
+
+      match property.name() {
+        "BEGIN" if depth > 0 || timezone => depth += 1,
+        "END" if depth > 0 => depth -= 1,
+        _ if depth > 0 => {}
+        _ => {
+          other_properties.push(property);
+          continue;
+        }
+      }
+
+      timezone_properties.push(property);
+    }
+
+    let mut timezones = Timezones::new();
+
+    timezones.extend_from_components(&Component::from_properties(timezone_properties)?)?;
+
+    Calendar::from_properties(other_properties, &timezones)
+  }
+}
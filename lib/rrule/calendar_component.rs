@@ -0,0 +1,411 @@
+use super::{
+  calendar::Calendar,
+  datetime::DateTime,
+  rrule_set::RRuleSet,
+  timezone::{Timezone, Timezones},
+  value_type::ValueType,
+};
+use crate::{
+  error::Error,
+  serialization::{
+    component::Component,
+    parameters::Parameters,
+    properties::Properties,
+    property::{Property, Value},
+    text,
+  },
+};
+use std::{
+  collections::hash_map::DefaultHasher,
+  hash::{Hash, Hasher},
+  sync::atomic::{AtomicU64, Ordering},
+};
+
+/// Components which may recur.
+const RECURRING_COMPONENTS: [&str; 3] = ["VEVENT", "VTODO", "VJOURNAL"];
+
+/// Product identifier written to the generated iCalendar documents.
+const PRODID: &str = "-//rrule-rust//EN";
+
+/// Distinguishes the UIDs generated within the same instant.
+static UID_SEQUENCE: AtomicU64 = AtomicU64::new(0);
+
+/// Recurrence set of a VEVENT, VTODO or VJOURNAL component, along with the
+/// properties identifying it.
+#[derive(Clone)]
+pub struct CalendarComponent {
+  name: String,
+  uid: Option<String>,
+  summary: Option<String>,
+  rrule_set: RRuleSet,
+  dtend: Option<DateTime>,
+  duration: Option<String>,
+  dtstamp: Option<DateTime>,
+  /// Components nested in the component, such as VALARM, kept as parsed to
+  /// be written back.
+  components: Vec<Component>,
+}
+
+impl CalendarComponent {
+  /// Creates a VEVENT recurring as defined by `rrule_set`.
+  pub fn new(rrule_set: RRuleSet) -> Self {
+    Self {
+      name: "VEVENT".to_string(),
+      uid: None,
+      summary: None,
+      rrule_set,
+      dtend: None,
+      duration: None,
+      dtstamp: None,
+      components: Vec::new(),
+    }
+  }
+
+  pub fn set_uid(self, uid: Option<String>) -> Self {
+    Self { uid, ..self }
+  }
+
+  pub fn set_summary(self, summary: Option<String>) -> Self {
+    Self { summary, ..self }
+  }
+
+  /// Sets DTEND, which is written with the TZID of DTSTART unless in UTC.
+  pub fn set_dtend(self, dtend: Option<DateTime>) -> Result<Self, Error> {
+    if let Some(dtend) = &dtend {
+      let dtstart = self.rrule_set.dtstart();
+
+      if self.duration.is_some() {
+        return Err(Error::Validation(
+          "DTEND and DURATION are mutually exclusive".to_string(),
+        ));
+      }
+
+      if dtend.derive_value_type() != dtstart.derive_value_type() {
+        return Err(Error::DtStartValueTypeMismatch("DTEND".to_string()));
+      }
+
+      if let Some(time) = dtend.time() {
+        if time.offset().is_none() && dtstart.tzid().is_none() && !dtstart.is_floating() {
+          return Err(Error::MissingTimezone("DTEND".to_string()));
+        }
+      }
+
+      let disambiguation = self.rrule_set.disambiguation();
+
+      if dtend.to_datetime(&dtstart.derive_timezone(), disambiguation)?
+        <= dtstart.to_datetime(disambiguation)?
+      {
+        return Err(Error::Validation(
+          "DTEND must be later than DTSTART".to_string(),
+        ));
+      }
+    }
+
+    Ok(Self { dtend, ..self })
+  }
+
+  /// Sets DURATION, e.g. `PT1H30M`. Durations of all-day components must be
+  /// given in days or weeks.
+  pub fn set_duration(self, duration: Option<String>) -> Result<Self, Error> {
+    if let Some(duration) = &duration {
+      if self.dtend.is_some() {
+        return Err(Error::Validation(
+          "DTEND and DURATION are mutually exclusive".to_string(),
+        ));
+      }
+
+      if !is_duration(duration) {
+        return Err(Error::invalid_property_value("DURATION", duration));
+      }
+
+      if self.rrule_set.dtstart().derive_value_type() == ValueType::Date && duration.contains('T') {
+        return Err(Error::DtStartValueTypeMismatch("DURATION".to_string()));
+      }
+    }
+
+    Ok(Self { duration, ..self })
+  }
+
+  /// Sets DTSTAMP, which must be in UTC. Defaults to the current time.
+  pub fn set_dtstamp(self, dtstamp: Option<DateTime>) -> Result<Self, Error> {
+    if let Some(dtstamp) = &dtstamp {
+      if dtstamp.time().as_ref().and_then(|time| time.offset()) != Some(0) {
+        return Err(Error::invalid_property_value(
+          "DTSTAMP",
+          &dtstamp.to_string(),
+        ));
+      }
+    }
+
+    Ok(Self { dtstamp, ..self })
+  }
+
+  /// Sets the components nested in the component, such as VALARM.
+  pub fn set_components(self, components: Vec<Component>) -> Self {
+    Self { components, ..self }
+  }
+
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  pub fn uid(&self) -> Option<&String> {
+    self.uid.as_ref()
+  }
+
+  pub fn summary(&self) -> Option<&String> {
+    self.summary.as_ref()
+  }
+
+  pub fn rrule_set(&self) -> &RRuleSet {
+    &self.rrule_set
+  }
+
+  pub fn components(&self) -> &Vec<Component> {
+    &self.components
+  }
+
+  /// Parses the VEVENT, VTODO and VJOURNAL components of an iCalendar
+  /// document, either nested in VCALENDAR or at the top level. Components
+  /// without DTSTART are skipped, as they can't recur. TZIDs which don't name
+  /// an IANA time zone are resolved through the VTIMEZONE components. Nested
+  /// components, such as VALARM, are kept as parsed.
+  pub fn parse_all(str: &str) -> Result<Vec<Self>, Error> {
+    let properties: Properties = str.parse()?;
+    let mut components = Vec::new();
+    let document = Component::from_properties(properties)?;
+    let mut timezones = Timezones::new();
+
+    timezones.extend_from_components(&document)?;
+
+    for component in document.iter() {
+      match component.name() {
+        "VCALENDAR" => {
+          for component in component.components() {
+            components.extend(Self::from_component(component, &timezones)?);
+          }
+        }
+        _ => components.extend(Self::from_component(component, &timezones)?),
+      }
+    }
+
+    Ok(components)
+  }
+
+  fn from_component(component: &Component, timezones: &Timezones) -> Result<Option<Self>, Error> {
+    if !RECURRING_COMPONENTS.contains(&component.name()) || component.property("DTSTART").is_none()
+    {
+      return Ok(None);
+    }
+
+    let calendar = Calendar::from_properties(component.properties().iter().cloned(), timezones)?;
+    let rrule_set = RRuleSet::from_calendar(calendar).map_err(|err| component.locate(err))?;
+    let text_of = |name: &str| {
+      component
+        .property(name)
+        .map(|property| text::unescape(&property.value().to_string()))
+    };
+
+    Ok(Some(Self {
+      name: component.name().to_string(),
+      uid: text_of("UID"),
+      summary: text_of("SUMMARY"),
+      rrule_set,
+      dtend: None,
+      duration: None,
+      dtstamp: None,
+      components: component.components().clone(),
+    }))
+  }
+
+  /// Writes the component, wrapped in VCALENDAR, as a complete iCalendar
+  /// document with folded, CRLF terminated content lines. UID and DTSTAMP are
+  /// generated unless given or kept among the unknown properties of the set.
+  /// Every time zone named by a TZID is written as a VTIMEZONE component:
+  /// custom ones as given, others generated over the years the set spans.
+  pub fn to_ics(&self) -> String {
+    let unknown_properties = self.rrule_set.unknown_properties();
+    let is_unknown = |name: &str| unknown_properties.iter().any(|p| p.name() == name);
+    let is_overridden = |property: &Property| match property.name() {
+      "UID" => self.uid.is_some(),
+      "SUMMARY" => self.summary.is_some(),
+      "DTSTAMP" => self.dtstamp.is_some(),
+      "DTEND" | "DURATION" => self.dtend.is_some() || self.duration.is_some(),
+      _ => false,
+    };
+
+    let mut component = Component::new(self.name.clone());
+
+    match &self.uid {
+      Some(uid) => component.push_property(single("UID", uid.clone())),
+      None if !is_unknown("UID") => component.push_property(single("UID", generate_uid(self))),
+      None => {}
+    }
+
+    match &self.dtstamp {
+      Some(dtstamp) => component.push_property(single("DTSTAMP", dtstamp.to_string())),
+      None if !is_unknown("DTSTAMP") => {
+        let now: DateTime = (&chrono::Utc::now().with_timezone(&Timezone::UTC)).into();
+
+        component.push_property(single("DTSTAMP", now.to_string()));
+      }
+      None => {}
+    }
+
+    if let Some(summary) = &self.summary {
+      component.push_property(single("SUMMARY", text::escape(summary)));
+    }
+
+    for property in self.rrule_set.to_properties() {
+      if is_overridden(&property) {
+        continue;
+      }
+
+      let is_dtstart = property.name() == "DTSTART";
+
+      component.push_property(property);
+
+      if is_dtstart {
+        if let Some(property) = self.end_property() {
+          component.push_property(property);
+        }
+      }
+    }
+
+    for nested in self.components.iter() {
+      component.push_component(nested.clone());
+    }
+
+    let mut calendar = Component::new("VCALENDAR".to_string());
+
+    calendar.push_property(single("VERSION", "2.0".to_string()));
+    calendar.push_property(single("PRODID", PRODID.to_string()));
+
+    let (from, until) = self.years();
+
+    for timezone in self.rrule_set.timezones() {
+      if let Some(component) = timezone.to_component(from, until) {
+        calendar.push_component(component);
+      }
+    }
+
+    calendar.push_component(component);
+
+    calendar.to_properties().to_content_lines()
+  }
+
+  /// Returns the years from the earliest DTSTART, EXDATE or RDATE value to
+  /// the last occurrence, or `None` as the last one if it can't be told
+  /// without iterating the set, e.g. of an infinite set.
+  fn years(&self) -> (i32, Option<i32>) {
+    let rrule_set = &self.rrule_set;
+    let from = std::iter::once(rrule_set.dtstart().value())
+      .chain(
+        rrule_set
+          .exdates()
+          .iter()
+          .flat_map(|exdate| exdate.values()),
+      )
+      .chain(rrule_set.rdates().iter().flat_map(|rdate| rdate.values()))
+      .map(|datetime| datetime.year() as i32)
+      .min()
+      .unwrap_or_default();
+    let until = rrule_set.last_year().ok().flatten();
+
+    (from, until.map(|until| until.max(from)))
+  }
+
+  /// Returns the DTEND or DURATION property, if any.
+  fn end_property(&self) -> Option<Property> {
+    if let Some(duration) = &self.duration {
+      return Some(single("DURATION", duration.clone()));
+    }
+
+    let dtend = self.dtend.as_ref()?;
+    let dtstart = self.rrule_set.dtstart();
+    let mut parameters = Parameters::new();
+    let has_offset = dtend
+      .time()
+      .as_ref()
+      .and_then(|time| time.offset())
+      .is_some();
+
+    if let (Some(tzid), false) = (dtstart.tzid(), has_offset) {
+      parameters.insert("TZID".to_string(), tzid.to_string());
+    }
+
+    if let Some(value_type) = dtstart.value_type() {
+      parameters.insert("VALUE".to_string(), value_type.to_string());
+    }
+
+    Some(Property::new(
+      "DTEND".to_string(),
+      parameters,
+      Value::Single(dtend.to_property_value()),
+    ))
+  }
+}
+
+fn single(name: &str, value: String) -> Property {
+  Property::new(name.to_string(), Parameters::new(), Value::Single(value))
+}
+
+/// Generates a UID unique to the component and the current instant.
+fn generate_uid(component: &CalendarComponent) -> String {
+  let now = chrono::Utc::now();
+  let mut hasher = DefaultHasher::new();
+
+  component.rrule_set.to_string().hash(&mut hasher);
+  now.timestamp_nanos_opt().hash(&mut hasher);
+  UID_SEQUENCE
+    .fetch_add(1, Ordering::Relaxed)
+    .hash(&mut hasher);
+
+  format!(
+    "{}-{:016x}@rrule-rust",
+    now.format("%Y%m%dT%H%M%SZ"),
+    hasher.finish()
+  )
+}
+
+/// Returns whether `str` is a duration as defined by RFC 5545, e.g. `P1W`,
+/// `P1DT12H` or `-PT15M`.
+fn is_duration(str: &str) -> bool {
+  let str = str.strip_prefix(['+', '-']).unwrap_or(str);
+  let Some(str) = str.strip_prefix('P') else {
+    return false;
+  };
+  let mut designators = String::new();
+  let mut digits = 0;
+
+  for char in str.chars() {
+    match char {
+      '0'..='9' => digits += 1,
+      'T' if digits == 0 => designators.push(char),
+      'W' | 'D' | 'H' | 'M' | 'S' if digits > 0 => {
+        designators.push(char);
+        digits = 0;
+      }
+      _ => return false,
+    }
+  }
+
+  digits == 0
+    && matches!(
+      designators.as_str(),
+      "W"
+        | "D"
+        | "DTH"
+        | "DTHM"
+        | "DTHMS"
+        | "DTM"
+        | "DTMS"
+        | "DTS"
+        | "TH"
+        | "THM"
+        | "THMS"
+        | "TM"
+        | "TMS"
+        | "TS"
+    )
+}
@@ -2,14 +2,22 @@ use std::fmt;
 use std::str::FromStr;
 
 use chrono::Datelike;
+use chrono::LocalResult;
 use chrono::Offset;
-use chrono::TimeZone;
 use chrono::Timelike;
 use napi::bindgen_prelude::Int32Array;
 
+use crate::error::Error;
+use crate::rrule::disambiguation::Disambiguation;
 use crate::rrule::time::Time;
+use crate::rrule::timezone::Timezone;
 use crate::rrule::value_type::ValueType;
 
+/// Stands for the missing offset of a floating time in the arrays exchanged
+/// with JavaScript. It is out of the range of offsets, so that any offset in
+/// seconds, including -1, can be told from it.
+pub const NO_OFFSET: i32 = i32::MIN;
+
 #[derive(Clone)]
 pub struct DateTime {
   pub year: u32,
@@ -35,29 +43,99 @@ impl DateTime {
     &self.time
   }
 
+  /// Returns whether the datetime is a floating time, i.e. has a time which
+  /// is not bound to any offset.
+  pub fn is_floating(&self) -> bool {
+    matches!(&self.time, Some(time) if time.offset.is_none())
+  }
+
+  /// Converts the datetime into `timezone`, unless its offset binds it to
+  /// UTC or a fixed offset. Wall-clock times in a DST gap or overlap are
+  /// resolved by `disambiguation`.
   pub fn to_datetime(
     &self,
-    timezone: &chrono_tz::Tz,
-  ) -> Result<chrono::DateTime<chrono_tz::Tz>, String> {
+    timezone: &Timezone,
+    disambiguation: Disambiguation,
+  ) -> Result<chrono::DateTime<Timezone>, Error> {
     let timezone = match &self.time {
-      Some(time) => match time.offset {
-        Some(0) => &chrono_tz::Tz::UTC,
-        _ => timezone,
-      },
-      None => timezone,
+      Some(time) => time.timezone()?.unwrap_or_else(|| timezone.clone()),
+      None => timezone.clone(),
     };
 
+    match disambiguation.resolve(&timezone, &self.to_naive()?) {
+      LocalResult::Single(datetime) => Ok(datetime),
+      LocalResult::Ambiguous(_, _) => Err(Error::AmbiguousLocalTime {
+        datetime: self.to_string(),
+        tzid: timezone.to_string(),
+      }),
+      LocalResult::None => Err(Error::NonexistentLocalTime {
+        datetime: self.to_string(),
+        tzid: timezone.to_string(),
+      }),
+    }
+  }
+
+  /// Returns the date and time of the datetime, regardless of its offset.
+  pub fn to_naive(&self) -> Result<chrono::NaiveDateTime, Error> {
     let (hour, minute, second) = match &self.time {
       Some(time) => (time.hour, time.minute, time.second),
       None => (0, 0, 0),
     };
 
-    match timezone
-      .with_ymd_and_hms(self.year as i32, self.month, self.day, hour, minute, second)
-      .single()
-    {
-      Some(datetime) => Ok(datetime),
-      None => Err(format!("Invalid datetime: {}", self)),
+    chrono::NaiveDate::from_ymd_opt(self.year as i32, self.month, self.day)
+      .and_then(|date| date.and_hms_opt(hour, minute, second))
+      .ok_or_else(|| Error::invalid_value("datetime", &self.to_string()))
+  }
+
+  /// Moves a datetime with a fixed offset into the wall-clock time of
+  /// `timezone`, keeping the instant. Others are returned as is.
+  pub fn into_wall_clock(self, timezone: &Timezone) -> Result<Self, Error> {
+    match &self.time {
+      Some(time) if time.offset.is_some_and(|offset| offset != 0) => {
+        let datetime: DateTime = (&self
+          .to_datetime(timezone, Disambiguation::Reject)?
+          .with_timezone(timezone))
+          .into();
+
+        Ok(DateTime {
+          time: datetime.time.map(|time| Time {
+            offset: None,
+            ..time
+          }),
+          ..datetime
+        })
+      }
+      _ => Ok(self),
+    }
+  }
+
+  /// Formats the datetime as the value of an iCalendar property. RFC 5545
+  /// has no syntax for fixed offsets, so such datetimes are written in UTC.
+  pub fn to_property_value(&self) -> String {
+    let offset = match &self.time {
+      Some(time) => time.offset.unwrap_or(0),
+      None => 0,
+    };
+    let utc = self
+      .to_naive()
+      .ok()
+      .filter(|_| offset != 0)
+      .and_then(|naive| naive.checked_sub_signed(chrono::Duration::seconds(offset.into())));
+
+    match utc {
+      Some(utc) => DateTime {
+        year: utc.year() as u32,
+        month: utc.month(),
+        day: utc.day(),
+        time: Some(Time {
+          hour: utc.hour(),
+          minute: utc.minute(),
+          second: utc.second(),
+          offset: Some(0),
+        }),
+      }
+      .to_string(),
+      None => self.to_string(),
     }
   }
 
@@ -68,53 +146,25 @@ impl DateTime {
     }
   }
 
-  fn from_str(str: &str) -> Result<Self, String> {
-    if !(str.len() == 8 || (str.len() <= 16 && str.len() >= 15)) {
-      return Err(format!("Invalid datetime string: {}", str));
+  fn from_str(str: &str) -> Result<Self, Error> {
+    if !matches!(str.len(), 8 | 15 | 16 | 20 | 22) {
+      return Err(Error::invalid_value("datetime", str));
     }
 
-    let year = str
-      .get(0..4)
-      .ok_or(format!("Can not extract year from: {}", str))?;
-    let year: u32 = year
-      .parse()
-      .map_err(|_| format!("Invalid year: {}", year))?;
-
-    let month = str
-      .get(4..6)
-      .ok_or(format!("Can not extract month from: {}", str))?;
-    let month: u32 = month
-      .parse()
-      .map_err(|_| format!("Invalid month: {}", month))?;
-    let day = str
-      .get(6..8)
-      .ok_or(format!("Can not extract day from: {}", str))?;
-    let day: u32 = day.parse().map_err(|_| format!("Invalid day: {}", day))?;
+    let year = parse_component(str, 0..4)?;
+    let month = parse_component(str, 4..6)?;
+    let day = parse_component(str, 6..8)?;
 
     if str.len() > 8 {
-      let hour = str
-        .get(9..11)
-        .ok_or(format!("Can not extract hour from: {}", str))?;
-      let hour: u32 = hour
-        .parse()
-        .map_err(|_| format!("Invalid hour: {}", hour))?;
-
-      let minute = str
-        .get(11..13)
-        .ok_or(format!("Can not extract minute from: {}", str))?;
-      let minute: u32 = minute
-        .parse()
-        .map_err(|_| format!("Invalid minute: {}", minute))?;
-
-      let second = str
-        .get(13..15)
-        .ok_or(format!("Can not extract second from: {}", str))?;
-      let second: u32 = second
-        .parse()
-        .map_err(|_| format!("Invalid second: {}", second))?;
-
-      let utc = str.get(15..16).unwrap_or("").to_uppercase() == "Z";
-      let offset = if utc { Some(0) } else { None };
+      let hour = parse_component(str, 9..11)?;
+      let minute = parse_component(str, 11..13)?;
+      let second = parse_component(str, 13..15)?;
+
+      let offset = match str.len() {
+        20 | 22 => Some(parse_offset(str)?),
+        _ if str.get(15..16).unwrap_or("").to_uppercase() == "Z" => Some(0),
+        _ => None,
+      };
 
       return Ok(Self {
         year,
@@ -151,7 +201,7 @@ impl From<(i32, i32, i32, i32, i32, i32, i32)> for DateTime {
           minute: arr.4 as u32,
           second: arr.5 as u32,
           offset: match arr.6 {
-            -1 => None,
+            NO_OFFSET => None,
             offset => Some(offset),
           },
         }),
@@ -169,7 +219,7 @@ impl From<Int32Array> for DateTime {
     let minute = arr[4];
     let second = arr[5];
     let offset = match arr[6] {
-      -1 => None,
+      NO_OFFSET => None,
       offset => Some(offset),
     };
 
@@ -195,33 +245,12 @@ impl From<Int32Array> for DateTime {
 // TODO: chrono datetime is alwats converted into DateTime with Time
 // Probabbly there should be a method to convert into DateTime without Time
 // And this trait must me removed
-impl From<&chrono::DateTime<chrono_tz::Tz>> for DateTime {
-  fn from(datetime: &chrono::DateTime<chrono_tz::Tz>) -> Self {
-    let offset = datetime.offset().fix().local_minus_utc();
-    let year = datetime.year() as u32;
-    let month = datetime.month();
-    let day = datetime.day();
-    let hour = datetime.hour();
-    let minute = datetime.minute();
-    let second = datetime.second();
-
-    DateTime {
-      year,
-      month,
-      day,
-      time: Some(Time {
-        hour,
-        minute,
-        second,
-        offset: Some(offset),
-      }),
-    }
-  }
-}
-
-impl From<&chrono::DateTime<rrule::Tz>> for DateTime {
-  fn from(datetime: &chrono::DateTime<rrule::Tz>) -> Self {
-    let offset = datetime.offset().fix().local_minus_utc();
+impl From<&chrono::DateTime<Timezone>> for DateTime {
+  fn from(datetime: &chrono::DateTime<Timezone>) -> Self {
+    let offset = match datetime.timezone() {
+      Timezone::Floating => None,
+      _ => Some(datetime.offset().fix().local_minus_utc()),
+    };
     let year = datetime.year() as u32;
     let month = datetime.month();
     let day = datetime.day();
@@ -233,12 +262,11 @@ impl From<&chrono::DateTime<rrule::Tz>> for DateTime {
       year,
       month,
       day,
-
       time: Some(Time {
         hour,
         minute,
         second,
-        offset: Some(offset),
+        offset,
       }),
     }
   }
@@ -263,15 +291,15 @@ impl From<&DateTime> for Int32Array {
         None => -1,
       },
       match &val.time {
-        Some(time) => time.offset.unwrap_or(-1),
-        None => -1,
+        Some(time) => time.offset.unwrap_or(NO_OFFSET),
+        None => NO_OFFSET,
       },
     ])
   }
 }
 
 impl FromStr for DateTime {
-  type Err = String;
+  type Err = Error;
 
   fn from_str(str: &str) -> Result<Self, Self::Err> {
     DateTime::from_str(str)
@@ -290,8 +318,9 @@ impl fmt::Display for DateTime {
         time.minute,
         time.second,
         match time.offset {
-          Some(0) => "Z",
-          _ => "",
+          Some(0) => "Z".to_string(),
+          Some(offset) => format_offset(offset),
+          None => String::new(),
         }
       ),
       None => format!("{:04}{:02}{:02}", self.year, self.month, self.day),
@@ -300,3 +329,44 @@ impl fmt::Display for DateTime {
     write!(f, "{}", string)
   }
 }
+
+/// Formats an offset in seconds as `+HHMM`, or `+HHMMSS` if it has seconds.
+pub fn format_offset(offset: i32) -> String {
+  let sign = if offset < 0 { '-' } else { '+' };
+  let offset = offset.unsigned_abs();
+  let (hours, minutes, seconds) = (offset / 3600, offset / 60 % 60, offset % 60);
+
+  match seconds {
+    0 => format!("{}{:02}{:02}", sign, hours, minutes),
+    _ => format!("{}{:02}{:02}{:02}", sign, hours, minutes, seconds),
+  }
+}
+
+/// Parses the `+HHMM[SS]` offset following the time of a datetime string.
+fn parse_offset(str: &str) -> Result<i32, Error> {
+  let sign = match str.get(15..16) {
+    Some("+") => 1,
+    Some("-") => -1,
+    _ => return Err(Error::invalid_value("datetime", str)),
+  };
+  let hours = parse_component(str, 16..18)?;
+  let minutes = parse_component(str, 18..20)?;
+  let seconds = match str.len() {
+    22 => parse_component(str, 20..22)?,
+    _ => 0,
+  };
+
+  if minutes > 59 || seconds > 59 {
+    return Err(Error::invalid_value("datetime", str));
+  }
+
+  Ok(sign * (hours * 3600 + minutes * 60 + seconds) as i32)
+}
+
+/// Parses the digits of a datetime string at `range`.
+fn parse_component(str: &str, range: std::ops::Range<usize>) -> Result<u32, Error> {
+  str
+    .get(range)
+    .and_then(|component| component.parse().ok())
+    .ok_or_else(|| Error::invalid_value("datetime", str))
+}
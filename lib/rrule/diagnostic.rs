@@ -0,0 +1,79 @@
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Severity {
+  Error,
+  Warning,
+}
+
+impl fmt::Display for Severity {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let str = match self {
+      Severity::Error => "error",
+      Severity::Warning => "warning",
+    };
+
+    write!(f, "{}", str)
+  }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum DiagnosticCode {
+  /// No calendar date satisfies the BYMONTH, BYMONTHDAY, BYYEARDAY and BYDAY
+  /// parts at once, so the rule never produces an occurrence.
+  NoMatchingDate,
+  /// BYWEEKNO is only valid in YEARLY rules.
+  ByWeekNoOutsideYearly,
+  /// BYYEARDAY must not be used in MONTHLY, WEEKLY and DAILY rules.
+  ByYearDayWithFrequency,
+  /// Numeric BYDAY values must not be used in WEEKLY rules.
+  NumericByDayInWeekly,
+  /// BYSETPOS must be used along with another BYxxx part.
+  BySetPosWithoutByRule,
+  /// COUNT and UNTIL must not occur in the same rule.
+  CountAndUntil,
+  /// UNTIL must be specified in UTC when DTSTART has a TZID.
+  UntilNotUtc,
+}
+
+impl fmt::Display for DiagnosticCode {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let str = match self {
+      DiagnosticCode::NoMatchingDate => "NO_MATCHING_DATE",
+      DiagnosticCode::ByWeekNoOutsideYearly => "BYWEEKNO_OUTSIDE_YEARLY",
+      DiagnosticCode::ByYearDayWithFrequency => "BYYEARDAY_WITH_FREQUENCY",
+      DiagnosticCode::NumericByDayInWeekly => "NUMERIC_BYDAY_IN_WEEKLY",
+      DiagnosticCode::BySetPosWithoutByRule => "BYSETPOS_WITHOUT_BY_RULE",
+      DiagnosticCode::CountAndUntil => "COUNT_AND_UNTIL",
+      DiagnosticCode::UntilNotUtc => "UNTIL_NOT_UTC",
+    };
+
+    write!(f, "{}", str)
+  }
+}
+
+/// Describes a problem found while analyzing a rule.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Diagnostic {
+  pub severity: Severity,
+  pub code: DiagnosticCode,
+  pub message: String,
+}
+
+impl Diagnostic {
+  pub fn error(code: DiagnosticCode, message: String) -> Self {
+    Self {
+      severity: Severity::Error,
+      code,
+      message,
+    }
+  }
+
+  pub fn warning(code: DiagnosticCode, message: String) -> Self {
+    Self {
+      severity: Severity::Warning,
+      code,
+      message,
+    }
+  }
+}
@@ -0,0 +1,132 @@
+use std::{fmt, str::FromStr};
+
+use chrono::{Duration, MappedLocalTime, NaiveDateTime, Offset, TimeZone};
+
+use super::timezone::Timezone;
+use crate::error::Error;
+
+/// Resolution of wall-clock times which fall into a DST gap or overlap, akin
+/// to the `disambiguation` option of Temporal.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Disambiguation {
+  /// Fails on such DTSTART, UNTIL, EXDATE and RDATE values. Generated
+  /// occurrences are resolved as by `ShiftForward` instead.
+  #[default]
+  Reject,
+  /// Takes the earlier time of an overlap. Times in a gap are moved back by
+  /// its length.
+  Earlier,
+  /// Takes the later time of an overlap. Times in a gap are moved forward by
+  /// its length.
+  Later,
+  /// Takes the earlier time of an overlap, while times in a gap are moved
+  /// forward by its length, as RFC 5545 requires for generated occurrences.
+  ShiftForward,
+}
+
+impl Disambiguation {
+  /// Resolves the wall-clock time `local` in `timezone`. Only `Reject` leaves
+  /// times in a gap or an overlap unresolved.
+  pub fn resolve(
+    &self,
+    timezone: &Timezone,
+    local: &NaiveDateTime,
+  ) -> MappedLocalTime<chrono::DateTime<Timezone>> {
+    // Transitions are assumed to be at least a day apart, as for custom time
+    // zones, so the offsets around a gap are the ones a day off
+    let shift = |days: i64| {
+      let offset = timezone
+        .offset_from_utc_datetime(&(*local + Duration::days(days)))
+        .fix();
+
+      MappedLocalTime::Single(timezone.from_utc_datetime(&(*local - offset)))
+    };
+
+    match (self, timezone.from_local_datetime(local)) {
+      (Disambiguation::Reject, mapped) => mapped,
+      (_, MappedLocalTime::Single(datetime)) => MappedLocalTime::Single(datetime),
+      (
+        Disambiguation::Earlier | Disambiguation::ShiftForward,
+        MappedLocalTime::Ambiguous(earliest, _),
+      ) => MappedLocalTime::Single(earliest),
+      (Disambiguation::Later, MappedLocalTime::Ambiguous(_, latest)) => {
+        MappedLocalTime::Single(latest)
+      }
+      (Disambiguation::Earlier, MappedLocalTime::None) => shift(1),
+      (Disambiguation::Later | Disambiguation::ShiftForward, MappedLocalTime::None) => shift(-1),
+    }
+  }
+
+  /// Returns the earliest moment, as a naive UTC datetime, which wall-clock
+  /// times at or after `local` can be resolved to, however disambiguated.
+  pub fn earliest_instant(timezone: &Timezone, local: &NaiveDateTime) -> NaiveDateTime {
+    let offset = offsets_around(timezone, local)
+      .into_iter()
+      .max()
+      .unwrap_or(0);
+
+    *local - Duration::seconds(offset.into())
+  }
+
+  /// Returns the earliest wall-clock time which can be resolved to the moment
+  /// `utc` or later, however disambiguated.
+  pub fn earliest_local(timezone: &Timezone, utc: &NaiveDateTime) -> NaiveDateTime {
+    let offset = offsets_around(timezone, utc).into_iter().min().unwrap_or(0);
+
+    *utc + Duration::seconds(offset.into())
+  }
+
+  /// Resolves the wall-clock time `local` of a generated occurrence. As RFC
+  /// 5545 requires, `Reject` does not fail on them, but shifts times in a gap
+  /// forward by its length and takes the earlier time of an overlap.
+  pub fn resolve_occurrence(
+    &self,
+    timezone: &Timezone,
+    local: &NaiveDateTime,
+  ) -> Option<chrono::DateTime<Timezone>> {
+    let disambiguation = match self {
+      Disambiguation::Reject => Disambiguation::ShiftForward,
+      disambiguation => *disambiguation,
+    };
+
+    disambiguation.resolve(timezone, local).single()
+  }
+}
+
+/// Returns the offsets of `timezone` within a day of `datetime`, which cover
+/// the transition `datetime` may be close to.
+fn offsets_around(timezone: &Timezone, datetime: &NaiveDateTime) -> [i32; 3] {
+  [-1, 0, 1].map(|days| {
+    timezone
+      .offset_from_utc_datetime(&(*datetime + Duration::days(days)))
+      .fix()
+      .local_minus_utc()
+  })
+}
+
+impl FromStr for Disambiguation {
+  type Err = Error;
+
+  fn from_str(str: &str) -> Result<Self, Self::Err> {
+    match str {
+      "reject" => Ok(Disambiguation::Reject),
+      "earlier" => Ok(Disambiguation::Earlier),
+      "later" => Ok(Disambiguation::Later),
+      "shift-forward" => Ok(Disambiguation::ShiftForward),
+      _ => Err(Error::invalid_value("disambiguation", str)),
+    }
+  }
+}
+
+impl fmt::Display for Disambiguation {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let str = match self {
+      Disambiguation::Reject => "reject",
+      Disambiguation::Earlier => "earlier",
+      Disambiguation::Later => "later",
+      Disambiguation::ShiftForward => "shift-forward",
+    };
+
+    write!(f, "{}", str)
+  }
+}
@@ -1,5 +1,13 @@
-use super::datetime::DateTime;
+use chrono::TimeZone;
+
+use super::{
+  datetime::DateTime,
+  disambiguation::Disambiguation,
+  preserve::Preserve,
+  timezone::{Timezone, Timezones},
+};
 use crate::{
+  error::Error,
   rrule::value_type::ValueType,
   serialization::{parameters::Parameters, property},
 };
@@ -7,8 +15,10 @@ use crate::{
 #[derive(Clone)]
 pub struct DtStart {
   value: DateTime,
-  tzid: Option<chrono_tz::Tz>,
+  tzid: Option<Timezone>,
   value_type: Option<ValueType>,
+  /// Parameters other than TZID and VALUE, kept as parsed.
+  unknown_parameters: Parameters,
 }
 
 impl DtStart {
@@ -20,17 +30,73 @@ impl DtStart {
     &self.value_type
   }
 
-  pub fn tzid(&self) -> Option<&chrono_tz::Tz> {
+  pub fn unknown_parameters(&self) -> &Parameters {
+    &self.unknown_parameters
+  }
+
+  pub fn set_unknown_parameters(self, unknown_parameters: Parameters) -> Self {
+    Self {
+      unknown_parameters: unknown_parameters.without(&["TZID", "VALUE"]),
+      ..self
+    }
+  }
+
+  pub fn tzid(&self) -> Option<&Timezone> {
     self.tzid.as_ref()
   }
 
-  pub fn derive_timezone(&self) -> chrono_tz::Tz {
-    match self.tzid {
-      Some(tz) => tz,
-      None => chrono_tz::Tz::UTC,
+  /// Returns whether DTSTART is a floating time, in which case the set is
+  /// iterated in wall-clock time.
+  pub fn is_floating(&self) -> bool {
+    self.tzid.is_none() && self.value.is_floating()
+  }
+
+  /// Returns the time zone the set is iterated in: the one of TZID, or else
+  /// the one implied by the offset of DTSTART.
+  pub fn derive_timezone(&self) -> Timezone {
+    match (&self.tzid, &self.value.time) {
+      (Some(tz), _) => tz.clone(),
+      (None, Some(time)) => match time.timezone() {
+        Ok(Some(timezone)) => timezone,
+        Ok(None) => Timezone::Floating,
+        Err(_) => Timezone::UTC,
+      },
+      (None, None) => Timezone::UTC,
     }
   }
 
+  /// Binds a floating DTSTART to `timezone`, keeping its wall-clock time.
+  pub fn resolve_floating(self, timezone: &Timezone) -> Self {
+    match self.is_floating() {
+      true => Self {
+        tzid: Some(timezone.clone()),
+        ..self
+      },
+      false => self,
+    }
+  }
+
+  /// Moves DTSTART into `timezone` as its wall-clock time there, see
+  /// `RRuleSet::with_timezone`. Dates are returned as is.
+  pub fn with_timezone(
+    self,
+    timezone: &Timezone,
+    preserve: Preserve,
+    disambiguation: Disambiguation,
+  ) -> Result<Self, Error> {
+    if self.value.time().is_none() {
+      return Ok(self);
+    }
+
+    let origin = self.derive_timezone();
+
+    Ok(Self {
+      value: preserve.rezone(&self.value, &origin, &origin, timezone, disambiguation)?,
+      tzid: Some(timezone.clone()),
+      ..self
+    })
+  }
+
   pub fn derive_value_type(&self) -> ValueType {
     match self.value_type() {
       Some(vt) => vt.clone(),
@@ -38,14 +104,34 @@ impl DtStart {
     }
   }
 
-  pub fn to_datetime(&self) -> Result<chrono::DateTime<chrono_tz::Tz>, String> {
-    self.value.to_datetime(&self.derive_timezone())
+  pub fn to_datetime(
+    &self,
+    disambiguation: Disambiguation,
+  ) -> Result<chrono::DateTime<Timezone>, Error> {
+    self
+      .value
+      .to_datetime(&self.derive_timezone(), disambiguation)
+  }
+
+  /// Returns DTSTART as the recurrence engine iterates from it, i.e. its
+  /// wall-clock time, even if it falls into a DST gap or overlap.
+  pub fn to_engine(
+    &self,
+    disambiguation: Disambiguation,
+  ) -> Result<chrono::DateTime<rrule::Tz>, Error> {
+    let timezone = self.derive_timezone();
+    let datetime = self.to_datetime(disambiguation)?;
+
+    match self.value.time() {
+      Some(time) if time.offset().is_some() => Ok(timezone.to_engine(&datetime)),
+      _ => Ok(rrule::Tz::UTC.from_utc_datetime(&self.value.to_naive()?)),
+    }
   }
 
   pub fn to_property(&self) -> property::Property {
     let mut parameters = Parameters::new();
 
-    if let Some(tzid) = self.tzid {
+    if let Some(tzid) = &self.tzid {
       parameters.insert("TZID".to_string(), tzid.to_string());
     }
 
@@ -53,7 +139,9 @@ impl DtStart {
       parameters.insert("VALUE".to_string(), value.to_string());
     }
 
-    let value: String = self.value.to_string();
+    parameters.extend(&self.unknown_parameters);
+
+    let value: String = self.value.to_property_value();
 
     property::Property::new(
       "DTSTART".to_string(),
@@ -64,65 +152,69 @@ impl DtStart {
 
   pub fn new(
     value: DateTime,
-    tzid: Option<chrono_tz::Tz>,
+    tzid: Option<Timezone>,
     value_type: Option<ValueType>,
-  ) -> Result<Self, String> {
-    if let Some(time) = &value.time {
-      if time.offset() != Some(0) && tzid.is_none() {
-        return Err("TZID is requred for non-UTC DTSTART".to_string());
-      }
-    }
-
+  ) -> Result<Self, Error> {
     if let Some(vt) = &value_type {
       if vt != &value.derive_value_type() {
-        return Err("DTSTART value and value type do not match".to_string());
+        return Err(Error::ValueTypeMismatch("DTSTART".to_string()));
       }
     }
 
+    let value = match &tzid {
+      Some(tz) => value.into_wall_clock(tz)?,
+      None => {
+        if let Some(time) = &value.time {
+          time.timezone()?;
+        }
+
+        value
+      }
+    };
+
     Ok(Self {
       value,
       tzid,
       value_type,
+      unknown_parameters: Parameters::new(),
     })
   }
 
-  pub fn from_property(property: property::Property) -> Result<Self, String> {
+  /// Parses DTSTART, resolving its TZID through `timezones`.
+  pub fn from_property(
+    property: &property::Property,
+    timezones: &Timezones,
+  ) -> Result<Self, Error> {
     let value = match property.value() {
       property::Value::Single(value) => value,
-      _ => return Err("Invalid DTSTART value".to_string()),
+      value => return Err(Error::invalid_property_value("DTSTART", &value.to_string())),
     };
-    let value: DateTime = value.parse()?;
+    let value: DateTime = value
+      .parse()
+      .map_err(|_| Error::invalid_property_value("DTSTART", value))?;
 
     let tzid = match property.parameters().get("TZID") {
-      Some(value) => {
-        let tz: chrono_tz::Tz = value
-          .parse()
-          .map_err(|_| format!("Invalid timezone: {}", value))?;
-
-        Some(tz)
-      }
+      Some(value) => Some(timezones.resolve(value)?),
       None => None,
     };
 
     let value_type = match property.parameters().get("VALUE") {
       Some(value) => {
-        let value: ValueType = value
-          .parse()
-          .map_err(|_| format!("Invalid value: {}", value))?;
+        let value: ValueType = value.parse()?;
 
         Some(value)
       }
       None => None,
     };
 
-    Self::new(value, tzid, value_type)
+    Ok(Self::new(value, tzid, value_type)?.set_unknown_parameters(property.parameters().clone()))
   }
 }
 
 impl TryFrom<property::Property> for DtStart {
-  type Error = String;
+  type Error = Error;
 
   fn try_from(property: property::Property) -> Result<Self, Self::Error> {
-    DtStart::from_property(property)
+    DtStart::from_property(&property, &Timezones::new())
   }
 }
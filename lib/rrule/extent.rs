@@ -0,0 +1,11 @@
+use super::datetime::DateTime;
+
+/// Describes how far a recurrence set extends.
+#[derive(Clone)]
+pub enum Extent {
+  /// At least one RRULE is limited by neither COUNT nor UNTIL.
+  Infinite,
+  /// Every RRULE is limited by COUNT or UNTIL. The number of occurrences is
+  /// left to `RRuleSet::count`, as it may take iterating the whole set.
+  Finite { last: Option<DateTime> },
+}
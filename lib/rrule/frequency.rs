@@ -1,5 +1,10 @@
 use std::{fmt, str::FromStr};
 
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+
+use super::weekday::Weekday;
+use crate::error::Error;
+
 #[derive(Clone)]
 pub enum Frequency {
   Yearly,
@@ -12,11 +17,8 @@ pub enum Frequency {
 }
 
 impl Frequency {
-  pub fn from_str(str: &str) -> Result<Self, String> {
-    let str = str.to_uppercase();
-    let str = str.as_str();
-
-    match str {
+  pub fn from_str(str: &str) -> Result<Self, Error> {
+    match str.to_uppercase().as_str() {
       "DAILY" => Ok(Frequency::Daily),
       "HOURLY" => Ok(Frequency::Hourly),
       "MINUTELY" => Ok(Frequency::Minutely),
@@ -24,7 +26,80 @@ impl Frequency {
       "SECONDLY" => Ok(Frequency::Secondly),
       "WEEKLY" => Ok(Frequency::Weekly),
       "YEARLY" => Ok(Frequency::Yearly),
-      _ => Err(format!("Invalid frequency: {}", str)),
+      _ => Err(Error::invalid_value("FREQ", str)),
+    }
+  }
+
+  /// Returns the longest possible duration of a single period.
+  pub fn max_period_duration(&self) -> Duration {
+    match self {
+      Frequency::Yearly => Duration::days(366),
+      Frequency::Monthly => Duration::days(31),
+      Frequency::Weekly => Duration::weeks(1),
+      Frequency::Daily => Duration::days(1),
+      Frequency::Hourly => Duration::hours(1),
+      Frequency::Minutely => Duration::minutes(1),
+      Frequency::Secondly => Duration::seconds(1),
+    }
+  }
+
+  /// Returns the index of the period that contains `datetime`, counting from
+  /// the period that contains `origin`.
+  pub fn period_index(
+    &self,
+    origin: &NaiveDateTime,
+    datetime: &NaiveDateTime,
+    weekstart: &Weekday,
+  ) -> i64 {
+    match self {
+      Frequency::Yearly => i64::from(datetime.year() - origin.year()),
+      Frequency::Monthly => {
+        i64::from(datetime.year() - origin.year()) * 12 + i64::from(datetime.month0())
+          - i64::from(origin.month0())
+      }
+      Frequency::Weekly => {
+        let from = week_start(origin.date(), weekstart);
+        let to = week_start(datetime.date(), weekstart);
+
+        (to - from).num_days().div_euclid(7)
+      }
+      Frequency::Daily => (datetime.date() - origin.date()).num_days(),
+      Frequency::Hourly => (truncate(datetime, 3600) - truncate(origin, 3600)).num_seconds() / 3600,
+      Frequency::Minutely => (truncate(datetime, 60) - truncate(origin, 60)).num_seconds() / 60,
+      Frequency::Secondly => (truncate(datetime, 1) - truncate(origin, 1)).num_seconds(),
+    }
+  }
+
+  /// Returns the first moment of the period `index` periods after the one
+  /// that contains `origin`.
+  pub fn period_start(
+    &self,
+    origin: &NaiveDateTime,
+    index: i64,
+    weekstart: &Weekday,
+  ) -> Option<NaiveDateTime> {
+    match self {
+      Frequency::Yearly => {
+        let year = i32::try_from(i64::from(origin.year()) + index).ok()?;
+
+        NaiveDate::from_ymd_opt(year, 1, 1)?.and_hms_opt(0, 0, 0)
+      }
+      Frequency::Monthly => {
+        let month = i64::from(origin.year()) * 12 + i64::from(origin.month0()) + index;
+        let year = i32::try_from(month.div_euclid(12)).ok()?;
+
+        NaiveDate::from_ymd_opt(year, month.rem_euclid(12) as u32 + 1, 1)?.and_hms_opt(0, 0, 0)
+      }
+      Frequency::Weekly => week_start(origin.date(), weekstart)
+        .checked_add_signed(Duration::try_weeks(index)?)?
+        .and_hms_opt(0, 0, 0),
+      Frequency::Daily => origin
+        .date()
+        .checked_add_signed(Duration::try_days(index)?)?
+        .and_hms_opt(0, 0, 0),
+      Frequency::Hourly => truncate(origin, 3600).checked_add_signed(Duration::try_hours(index)?),
+      Frequency::Minutely => truncate(origin, 60).checked_add_signed(Duration::try_minutes(index)?),
+      Frequency::Secondly => truncate(origin, 1).checked_add_signed(Duration::try_seconds(index)?),
     }
   }
 }
@@ -70,7 +145,7 @@ impl From<Frequency> for String {
 }
 
 impl FromStr for Frequency {
-  type Err = String;
+  type Err = Error;
 
   fn from_str(str: &str) -> Result<Self, Self::Err> {
     Self::from_str(str)
@@ -92,3 +167,16 @@ impl fmt::Display for Frequency {
     write!(f, "{}", str)
   }
 }
+
+fn week_start(date: NaiveDate, weekstart: &Weekday) -> NaiveDate {
+  let weekstart: rrule::Weekday = weekstart.into();
+  let days = (7 + date.weekday().num_days_from_monday() - weekstart.num_days_from_monday()) % 7;
+
+  date - Duration::days(i64::from(days))
+}
+
+fn truncate(datetime: &NaiveDateTime, seconds: u32) -> NaiveDateTime {
+  let elapsed = datetime.num_seconds_from_midnight() / seconds * seconds;
+
+  datetime.date().and_time(NaiveTime::MIN) + Duration::seconds(i64::from(elapsed))
+}
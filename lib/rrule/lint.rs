@@ -0,0 +1,93 @@
+use super::{
+  diagnostic::{Diagnostic, DiagnosticCode},
+  dtstart::DtStart,
+  frequency::Frequency,
+  rrule::RRule,
+  value_type::ValueType,
+};
+
+/// Reports constructions RFC 5545 forbids or defines as unusual. Unlike
+/// `validation::validate`, the reported rules may still produce occurrences.
+pub fn lint(rrule: &RRule, dtstart: Option<&DtStart>) -> Vec<Diagnostic> {
+  let mut diagnostics = Vec::new();
+
+  if !rrule.by_weekno().is_empty() && !matches!(rrule.frequency(), Frequency::Yearly) {
+    diagnostics.push(Diagnostic::warning(
+      DiagnosticCode::ByWeekNoOutsideYearly,
+      format!(
+        "BYWEEKNO is only valid with FREQ=YEARLY, not FREQ={}",
+        rrule.frequency()
+      ),
+    ));
+  }
+
+  if !rrule.by_yearday().is_empty()
+    && matches!(
+      rrule.frequency(),
+      Frequency::Monthly | Frequency::Weekly | Frequency::Daily
+    )
+  {
+    diagnostics.push(Diagnostic::warning(
+      DiagnosticCode::ByYearDayWithFrequency,
+      format!("BYYEARDAY must not be used with FREQ={}", rrule.frequency()),
+    ));
+  }
+
+  if matches!(rrule.frequency(), Frequency::Weekly)
+    && rrule.by_weekday().iter().any(|nday| nday.n.is_some())
+  {
+    diagnostics.push(Diagnostic::warning(
+      DiagnosticCode::NumericByDayInWeekly,
+      "Numeric BYDAY values must not be used with FREQ=WEEKLY".to_string(),
+    ));
+  }
+
+  if !rrule.by_setpos().is_empty() && !has_by_rule(rrule) {
+    diagnostics.push(Diagnostic::warning(
+      DiagnosticCode::BySetPosWithoutByRule,
+      "BYSETPOS must be used in conjunction with another BYxxx rule part".to_string(),
+    ));
+  }
+
+  if rrule.count().is_some() && rrule.until().is_some() {
+    diagnostics.push(Diagnostic::warning(
+      DiagnosticCode::CountAndUntil,
+      "COUNT and UNTIL must not both be set".to_string(),
+    ));
+  }
+
+  if let (Some(until), Some(dtstart)) = (rrule.until(), dtstart) {
+    let is_utc = until
+      .time()
+      .as_ref()
+      .is_some_and(|time| time.offset() == Some(0));
+
+    if dtstart.tzid().is_some()
+      && dtstart.derive_value_type() == ValueType::DateTime
+      && until.derive_value_type() == ValueType::DateTime
+      && !is_utc
+    {
+      diagnostics.push(Diagnostic::warning(
+        DiagnosticCode::UntilNotUtc,
+        format!(
+          "UNTIL must be specified in UTC when DTSTART has a TZID, got {}",
+          until
+        ),
+      ));
+    }
+  }
+
+  diagnostics
+}
+
+/// Returns whether the rule has a BYxxx part other than BYSETPOS.
+fn has_by_rule(rrule: &RRule) -> bool {
+  !rrule.by_month().is_empty()
+    || !rrule.by_weekno().is_empty()
+    || !rrule.by_yearday().is_empty()
+    || !rrule.by_monthday().is_empty()
+    || !rrule.by_weekday().is_empty()
+    || !rrule.by_hour().is_empty()
+    || !rrule.by_minute().is_empty()
+    || !rrule.by_second().is_empty()
+}
@@ -1,5 +1,7 @@
 use std::{fmt, str::FromStr};
 
+use crate::error::Error;
+
 #[derive(Clone)]
 pub enum Month {
   January,
@@ -17,7 +19,7 @@ pub enum Month {
 }
 
 impl FromStr for Month {
-  type Err = String;
+  type Err = Error;
 
   fn from_str(month: &str) -> Result<Self, Self::Err> {
     match month {
@@ -33,7 +35,7 @@ impl FromStr for Month {
       "10" => Ok(Month::October),
       "11" => Ok(Month::November),
       "12" => Ok(Month::December),
-      _ => Err(format!("Invalid month number: {}", month)),
+      _ => Err(Error::invalid_value("month", month)),
     }
   }
 }
@@ -1,4 +1,5 @@
 use super::weekday::Weekday;
+use crate::error::Error;
 use std::str::FromStr;
 
 #[derive(Clone)]
@@ -51,7 +52,7 @@ impl From<&NWeekday> for String {
 }
 
 impl FromStr for NWeekday {
-  type Err = String;
+  type Err = Error;
 
   fn from_str(str: &str) -> Result<Self, Self::Err> {
     let weekday = extract_weekday(str)?;
@@ -65,7 +66,7 @@ impl FromStr for NWeekday {
   }
 }
 
-fn extract_weekday(str: &str) -> Result<Weekday, String> {
+fn extract_weekday(str: &str) -> Result<Weekday, Error> {
   let weekday = str
     .chars()
     .rev()
@@ -78,7 +79,7 @@ fn extract_weekday(str: &str) -> Result<Weekday, String> {
   weekday.parse()
 }
 
-fn extract_number(str: &str) -> Result<i16, String> {
+fn extract_number(str: &str) -> Result<i16, Error> {
   let number = str
     .chars()
     .take_while(|c| c.is_ascii_digit() || *c == '-')
@@ -86,5 +87,5 @@ fn extract_number(str: &str) -> Result<i16, String> {
 
   number
     .parse()
-    .map_err(|_| format!("Invalid number: {}", number))
+    .map_err(|_| Error::invalid_value("weekday", str))
 }
@@ -0,0 +1,77 @@
+use std::{fmt, str::FromStr};
+
+use chrono::{Datelike, Timelike};
+
+use super::{datetime::DateTime, disambiguation::Disambiguation, time::Time, timezone::Timezone};
+use crate::error::Error;
+
+/// What `RRuleSet::with_timezone` keeps when moving a set into another time
+/// zone.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Preserve {
+  /// Keeps the instants of the occurrences, so their wall-clock times change
+  /// along with the offset.
+  Instant,
+  /// Keeps the wall-clock times of the occurrences, e.g. a standup at 9am
+  /// stays at 9am, so their instants change along with the offset.
+  WallClock,
+}
+
+impl Preserve {
+  /// Moves `datetime`, read in `tzid`, along with a set iterated in `origin`
+  /// into `timezone`. Returns its wall-clock time in `timezone`, while dates
+  /// are returned as is.
+  pub fn rezone(
+    &self,
+    datetime: &DateTime,
+    tzid: &Timezone,
+    origin: &Timezone,
+    timezone: &Timezone,
+    disambiguation: Disambiguation,
+  ) -> Result<DateTime, Error> {
+    if datetime.time().is_none() {
+      return Ok(datetime.clone());
+    }
+
+    let instant = datetime.to_datetime(tzid, disambiguation)?;
+    let local = match self {
+      Preserve::Instant => instant.with_timezone(timezone).naive_local(),
+      Preserve::WallClock => instant.with_timezone(origin).naive_local(),
+    };
+
+    Ok(DateTime {
+      year: local.year() as u32,
+      month: local.month(),
+      day: local.day(),
+      time: Some(Time {
+        hour: local.hour(),
+        minute: local.minute(),
+        second: local.second(),
+        offset: None,
+      }),
+    })
+  }
+}
+
+impl FromStr for Preserve {
+  type Err = Error;
+
+  fn from_str(str: &str) -> Result<Self, Self::Err> {
+    match str {
+      "instant" => Ok(Preserve::Instant),
+      "wall-clock" => Ok(Preserve::WallClock),
+      _ => Err(Error::invalid_value("preserve", str)),
+    }
+  }
+}
+
+impl fmt::Display for Preserve {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let str = match self {
+      Preserve::Instant => "instant",
+      Preserve::WallClock => "wall-clock",
+    };
+
+    write!(f, "{}", str)
+  }
+}
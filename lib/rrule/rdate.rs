@@ -1,5 +1,12 @@
-use super::{datetime::DateTime, dtstart::DtStart};
+use super::{
+  datetime::DateTime,
+  disambiguation::Disambiguation,
+  dtstart::DtStart,
+  preserve::Preserve,
+  timezone::{Timezone, Timezones},
+};
 use crate::{
+  error::Error,
   rrule::value_type::ValueType,
   serialization::{
     parameters::Parameters,
@@ -10,16 +17,18 @@ use crate::{
 #[derive(Clone)]
 pub struct RDate {
   values: Vec<DateTime>,
-  tzid: Option<chrono_tz::Tz>,
+  tzid: Option<Timezone>,
   value_type: Option<ValueType>,
+  /// Parameters other than TZID and VALUE, kept as parsed.
+  unknown_parameters: Parameters,
 }
 
 impl RDate {
   pub fn new(
     datetimes: Vec<DateTime>,
-    tzid: Option<chrono_tz::Tz>,
+    tzid: Option<Timezone>,
     value_type: Option<ValueType>,
-  ) -> Result<Self, String> {
+  ) -> Result<Self, Error> {
     let expected_value_type = match &value_type {
       Some(vt) => Some(vt.clone()),
       None => {
@@ -34,21 +43,39 @@ impl RDate {
     if let Some(vt) = &expected_value_type {
       for dt in &datetimes {
         if &dt.derive_value_type() != vt {
-          return Err(
-            "All RDATE instances must have the same value type as specified in RDATE".to_string(),
-          );
+          return Err(Error::MixedValueTypes("RDATE".to_string()));
         }
       }
     }
 
+    let datetimes = match &tzid {
+      Some(tz) => datetimes
+        .into_iter()
+        .map(|datetime| datetime.into_wall_clock(tz))
+        .collect::<Result<Vec<DateTime>, Error>>()?,
+      None => datetimes,
+    };
+
     Ok(Self {
       values: datetimes,
       tzid,
       value_type,
+      unknown_parameters: Parameters::new(),
     })
   }
 
-  pub fn tzid(&self) -> &Option<chrono_tz::Tz> {
+  pub fn unknown_parameters(&self) -> &Parameters {
+    &self.unknown_parameters
+  }
+
+  pub fn set_unknown_parameters(self, unknown_parameters: Parameters) -> Self {
+    Self {
+      unknown_parameters: unknown_parameters.without(&["TZID", "VALUE"]),
+      ..self
+    }
+  }
+
+  pub fn tzid(&self) -> &Option<Timezone> {
     &self.tzid
   }
 
@@ -60,10 +87,10 @@ impl RDate {
     &self.value_type
   }
 
-  pub fn derive_timezone(&self) -> chrono_tz::Tz {
-    match self.tzid {
-      Some(tz) => tz,
-      None => chrono_tz::Tz::UTC,
+  pub fn derive_timezone(&self) -> Timezone {
+    match &self.tzid {
+      Some(tz) => tz.clone(),
+      None => Timezone::UTC,
     }
   }
 
@@ -77,35 +104,81 @@ impl RDate {
     }
   }
 
+  /// Binds the values to `timezone`, keeping their wall-clock time, if all of
+  /// them are floating times.
+  pub fn resolve_floating(self, timezone: &Timezone) -> Self {
+    let floating = self.tzid.is_none()
+      && !self.values.is_empty()
+      && self.values.iter().all(DateTime::is_floating);
+
+    match floating {
+      true => Self {
+        tzid: Some(timezone.clone()),
+        ..self
+      },
+      false => self,
+    }
+  }
+
+  /// Moves the values along with a set iterated in `origin` into `timezone`
+  /// as their wall-clock times there, see `RRuleSet::with_timezone`. Dates
+  /// are returned as is.
+  pub fn with_timezone(
+    self,
+    origin: &Timezone,
+    timezone: &Timezone,
+    preserve: Preserve,
+    disambiguation: Disambiguation,
+  ) -> Result<Self, Error> {
+    if self.derive_value_type() != Some(ValueType::DateTime) {
+      return Ok(self);
+    }
+
+    let tzid = self.tzid.clone().unwrap_or_else(|| origin.clone());
+    let values = self
+      .values
+      .iter()
+      .map(|datetime| preserve.rezone(datetime, &tzid, origin, timezone, disambiguation))
+      .collect::<Result<Vec<DateTime>, Error>>()?;
+
+    Ok(Self {
+      values,
+      tzid: Some(timezone.clone()),
+      ..self
+    })
+  }
+
   pub fn to_datetimes(
     &self,
     dtstart: &DtStart,
-  ) -> Result<Vec<chrono::DateTime<chrono_tz::Tz>>, String> {
-    self.to_datetimes_with_fallback_tzid(dtstart.derive_timezone())
+    disambiguation: Disambiguation,
+  ) -> Result<Vec<chrono::DateTime<Timezone>>, Error> {
+    self.to_datetimes_with_fallback_tzid(dtstart.derive_timezone(), disambiguation)
   }
 
   pub fn to_datetimes_with_fallback_tzid(
     &self,
-    tzid: chrono_tz::Tz,
-  ) -> Result<Vec<chrono::DateTime<chrono_tz::Tz>>, String> {
+    tzid: Timezone,
+    disambiguation: Disambiguation,
+  ) -> Result<Vec<chrono::DateTime<Timezone>>, Error> {
     self
       .values
       .iter()
-      .map(|datetime| datetime.to_datetime(&self.tzid.unwrap_or(tzid)))
+      .map(|datetime| datetime.to_datetime(self.tzid.as_ref().unwrap_or(&tzid), disambiguation))
       .collect()
   }
 
   pub fn to_property(&self) -> Property {
     let mut parameters = Parameters::new();
 
-    if let Some(tzid) = self.tzid {
+    if let Some(tzid) = &self.tzid {
       parameters.insert("TZID".to_string(), tzid.to_string());
     }
 
     let value: String = self
       .values
       .iter()
-      .map(|datetime| datetime.to_string())
+      .map(|datetime| datetime.to_property_value())
       .collect::<Vec<String>>()
       .join(",");
 
@@ -117,49 +190,50 @@ impl RDate {
       parameters.insert("VALUE".to_string(), value.to_string());
     }
 
+    parameters.extend(&self.unknown_parameters);
+
     Property::new("RDATE".to_string(), parameters, Value::Single(value))
   }
 
-  pub fn from_property(property: Property) -> Result<Self, String> {
+  /// Parses RDATE, resolving its TZID through `timezones`.
+  pub fn from_property(property: &Property, timezones: &Timezones) -> Result<Self, Error> {
     let datetimes = match property.value() {
       Value::Single(value) => value,
-      _ => return Err("Invalid RDATE value".to_string()),
+      value => return Err(Error::invalid_property_value("RDATE", &value.to_string())),
     };
     let datetimes = datetimes
       .split(',')
-      .map(|date| date.parse::<DateTime>())
-      .collect::<Result<Vec<DateTime>, String>>()?;
+      .map(|date| {
+        date
+          .parse::<DateTime>()
+          .map_err(|_| Error::invalid_property_value("RDATE", date))
+      })
+      .collect::<Result<Vec<DateTime>, Error>>()?;
 
     let tzid = match property.parameters().get("TZID") {
-      Some(value) => {
-        let tz: chrono_tz::Tz = value
-          .parse()
-          .map_err(|_| format!("Invalid timezone: {}", value))?;
-
-        Some(tz)
-      }
+      Some(value) => Some(timezones.resolve(value)?),
       None => None,
     };
 
     let value_type = match property.parameters().get("VALUE") {
       Some(value) => {
-        let value: ValueType = value
-          .parse()
-          .map_err(|_| format!("Invalid value: {}", value))?;
+        let value: ValueType = value.parse()?;
 
         Some(value)
       }
       None => None,
     };
 
-    Self::new(datetimes, tzid, value_type)
+    Ok(
+      Self::new(datetimes, tzid, value_type)?.set_unknown_parameters(property.parameters().clone()),
+    )
   }
 }
 
 impl TryFrom<Property> for RDate {
-  type Error = String;
+  type Error = Error;
 
   fn try_from(property: Property) -> Result<Self, Self::Error> {
-    RDate::from_property(property)
+    RDate::from_property(&property, &Timezones::new())
   }
 }
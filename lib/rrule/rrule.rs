@@ -1,15 +1,41 @@
+use chrono::{Datelike, Duration, NaiveDateTime, Timelike};
 use itertools::Itertools;
-use std::{fmt, str::FromStr};
+use std::{fmt, ops::RangeInclusive, str::FromStr};
 
 use super::{
-  datetime::DateTime, dtstart::DtStart, frequency::Frequency, month::Month, n_weekday::NWeekday,
-  weekday::Weekday,
+  datetime::DateTime, diagnostic::Diagnostic, disambiguation::Disambiguation, dtstart::DtStart,
+  frequency::Frequency, lint, month::Month, n_weekday::NWeekday, preserve::Preserve,
+  timezone::Timezone, validation, weekday::Weekday,
 };
-use crate::serialization::{
-  parameters::Parameters,
-  property::{Property, Value},
+use crate::{
+  error::Error,
+  serialization::{
+    parameters::Parameters,
+    property::{Property, Value},
+  },
 };
 
+/// Latest year the recurrence engine produces occurrences in.
+const MAX_ENGINE_YEAR: i32 = 10_000;
+
+/// Rule parts interpreted by `RRule`, other parts are kept as parsed.
+const PARTS: [&str; 14] = [
+  "FREQ",
+  "INTERVAL",
+  "UNTIL",
+  "COUNT",
+  "WKST",
+  "BYDAY",
+  "BYHOUR",
+  "BYMINUTE",
+  "BYSECOND",
+  "BYMONTHDAY",
+  "BYYEARDAY",
+  "BYSETPOS",
+  "BYMONTH",
+  "BYWEEKNO",
+];
+
 #[derive(Clone)]
 pub struct RRule {
   until: Option<DateTime>,
@@ -26,6 +52,10 @@ pub struct RRule {
   by_setpos: Vec<i32>,
   by_month: Vec<Month>,
   by_weekno: Vec<i8>,
+  /// Parameters of the RRULE property, kept as parsed.
+  unknown_parameters: Parameters,
+  /// Rule parts other than `PARTS`, e.g. `X-NAME=...`, kept as parsed.
+  unknown_parts: Parameters,
 }
 
 impl RRule {
@@ -45,6 +75,8 @@ impl RRule {
       by_setpos: Vec::new(),
       by_month: Vec::new(),
       by_weekno: Vec::new(),
+      unknown_parameters: Parameters::new(),
+      unknown_parts: Parameters::new(),
     }
   }
 
@@ -60,6 +92,44 @@ impl RRule {
     Self { until, ..self }
   }
 
+  /// Converts a floating UNTIL into UTC, reading it as the wall-clock time of
+  /// `timezone`, which the floating DTSTART is bound to.
+  pub fn resolve_floating(self, timezone: &Timezone) -> Result<Self, Error> {
+    match &self.until {
+      Some(until) if until.is_floating() => {
+        let until = until
+          .to_datetime(timezone, Disambiguation::Reject)?
+          .with_timezone(&Timezone::UTC);
+
+        Ok(self.set_until(Some((&until).into())))
+      }
+      _ => Ok(self),
+    }
+  }
+
+  /// Moves UNTIL along with a set iterated in `origin` into `timezone`, see
+  /// `RRuleSet::with_timezone`. UNTIL is written in UTC, dates are kept.
+  pub fn with_timezone(
+    self,
+    origin: &Timezone,
+    timezone: &Timezone,
+    preserve: Preserve,
+    disambiguation: Disambiguation,
+  ) -> Result<Self, Error> {
+    let until = match &self.until {
+      Some(until) if until.time().is_some() => until,
+      _ => return Ok(self),
+    };
+    let until = match preserve {
+      Preserve::Instant => until.to_datetime(origin, disambiguation)?,
+      Preserve::WallClock => preserve
+        .rezone(until, origin, origin, timezone, disambiguation)?
+        .to_datetime(timezone, disambiguation)?,
+    };
+
+    Ok(self.set_until(Some((&until.with_timezone(&Timezone::UTC)).into())))
+  }
+
   pub fn set_by_hour(self, by_hour: Vec<u8>) -> Self {
     Self { by_hour, ..self }
   }
@@ -103,6 +173,28 @@ impl RRule {
     Self { weekstart, ..self }
   }
 
+  pub fn set_unknown_parameters(self, unknown_parameters: Parameters) -> Self {
+    Self {
+      unknown_parameters,
+      ..self
+    }
+  }
+
+  pub fn set_unknown_parts(self, unknown_parts: Parameters) -> Self {
+    Self {
+      unknown_parts: unknown_parts.without(&PARTS),
+      ..self
+    }
+  }
+
+  pub fn unknown_parameters(&self) -> &Parameters {
+    &self.unknown_parameters
+  }
+
+  pub fn unknown_parts(&self) -> &Parameters {
+    &self.unknown_parts
+  }
+
   pub fn frequency(&self) -> &Frequency {
     &self.frequency
   }
@@ -159,6 +251,94 @@ impl RRule {
     self.until.as_ref()
   }
 
+  /// Returns the latest moment at or before `datetime` which lies in a period
+  /// the rule iterates over, i.e. a period whose distance from the DTSTART
+  /// period is a multiple of INTERVAL.
+  ///
+  /// Returns `None` if the rule can only be iterated from DTSTART: COUNT
+  /// depends on every preceding occurrence, and MINUTELY/SECONDLY rules
+  /// filtered by BYHOUR/BYMINUTE skip whole hours and minutes regardless of
+  /// INTERVAL.
+  pub fn align(&self, origin: &NaiveDateTime, datetime: NaiveDateTime) -> Option<NaiveDateTime> {
+    let skips_periods = match self.frequency {
+      Frequency::Minutely => !self.by_hour.is_empty(),
+      Frequency::Secondly => !self.by_hour.is_empty() || !self.by_minute.is_empty(),
+      _ => false,
+    };
+
+    if self.count.is_some() || skips_periods {
+      return None;
+    }
+
+    let weekstart = self.weekstart.as_ref().unwrap_or_default();
+    let interval = i64::from(self.interval.unwrap_or(1).max(1));
+    let index = self.frequency.period_index(origin, &datetime, weekstart);
+    let offset = index.rem_euclid(interval);
+
+    if offset == 0 {
+      return Some(datetime);
+    }
+
+    self
+      .frequency
+      .period_start(origin, index - offset + 1, weekstart)?
+      .checked_sub_signed(Duration::seconds(1))
+  }
+
+  /// Returns the wall-clock time of the last occurrence of a rule which is
+  /// limited by COUNT alone and repeats `origin`, the wall-clock time of
+  /// DTSTART, every period of a fixed length.
+  ///
+  /// Returns `None` for other rules, which can only be iterated to find it:
+  /// BYxxx parts and months or years of varying length make the occurrences
+  /// irregular.
+  pub fn last_counted(&self, origin: &NaiveDateTime) -> Option<NaiveDateTime> {
+    let regular = matches!(
+      self.frequency,
+      Frequency::Weekly
+        | Frequency::Daily
+        | Frequency::Hourly
+        | Frequency::Minutely
+        | Frequency::Secondly
+    ) && self.by_weekday.is_empty()
+      && self.by_hour.is_empty()
+      && self.by_minute.is_empty()
+      && self.by_second.is_empty()
+      && self.by_monthday.is_empty()
+      && self.by_yearday.is_empty()
+      && self.by_setpos.is_empty()
+      && self.by_month.is_empty()
+      && self.by_weekno.is_empty();
+
+    if !regular || self.until.is_some() {
+      return None;
+    }
+
+    let steps =
+      i64::from(self.count?.checked_sub(1)?) * i64::from(self.interval.unwrap_or(1).max(1));
+    let seconds = self
+      .frequency
+      .max_period_duration()
+      .num_seconds()
+      .checked_mul(steps)?;
+    let last = origin.checked_add_signed(Duration::try_seconds(seconds)?)?;
+
+    (last.year() <= MAX_ENGINE_YEAR).then_some(last)
+  }
+
+  /// Detects combinations of parts that can never produce an occurrence,
+  /// e.g. `FREQ=YEARLY;BYMONTH=2;BYMONTHDAY=30`.
+  pub fn validate(&self) -> Vec<Diagnostic> {
+    validation::validate(self)
+  }
+
+  /// Reports constructions RFC 5545 forbids or defines as unusual, such as
+  /// COUNT and UNTIL used together. `dtstart` enables the checks depending on
+  /// the start of the set.
+  pub fn lint(&self, dtstart: Option<&DtStart>) -> Vec<Diagnostic> {
+    lint::lint(self, dtstart)
+  }
+
   pub fn to_property(&self) -> Property {
     let mut value = Parameters::new();
 
@@ -173,7 +353,7 @@ impl RRule {
     }
 
     if let Some(until) = &self.until {
-      value.insert("UNTIL".to_string(), until.to_string());
+      value.insert("UNTIL".to_string(), until.to_property_value());
     }
 
     if !self.by_hour.is_empty() {
@@ -226,53 +406,61 @@ impl RRule {
       value.insert("WKST".to_string(), weekstart.into());
     }
 
+    value.extend(&self.unknown_parts);
+
     Property::new(
       "RRULE".to_string(),
-      Parameters::new(),
+      self.unknown_parameters.clone(),
       Value::Parameters(value),
     )
   }
 
-  pub fn from_str(str: &str) -> Result<Self, String> {
-    let str = if !str.to_uppercase().starts_with("RRULE:") {
-      format!("RRULE:{}", str)
+  pub fn from_str(str: &str) -> Result<Self, Error> {
+    let property = if str.trim_start().to_uppercase().starts_with("RRULE:") {
+      Property::from_string(str)?
     } else {
-      str.to_string()
+      Property::from_value("RRULE", str)?
     };
 
-    let property: Property = str.parse().map_err(|err| format!("{}", err))?;
-
-    Self::from_property(property)
+    Self::from_property(&property).map_err(|err| property.locate(err))
   }
 
-  fn from_property(property: Property) -> Result<Self, String> {
+  pub fn from_property(property: &Property) -> Result<Self, Error> {
     if property.name() != "RRULE" && property.name() != "EXRULE" {
-      return Err(format!("Invalid property name: {}", property.name()));
+      return Err(Error::UnexpectedProperty(property.name().to_string()));
     }
 
     let value = match property.value() {
       Value::Parameters(value) => value,
-      Value::Single(value) => return Err(format!("Invalid {}: {}", property.name(), value)),
+      Value::Single(value) => return Err(Error::invalid_property_value(property.name(), value)),
     };
 
     let frequency = match value.get_as::<Frequency>("FREQ")? {
       Some(value) => value,
-      None => return Err("FREQ is required".to_string()),
+      None => return Err(Error::MissingProperty("FREQ".to_string())),
     };
 
     let interval = value.get_as::<u16>("INTERVAL")?;
     let until = value.get_as::<DateTime>("UNTIL")?;
     let count = value.get_as::<u32>("COUNT")?;
-    let by_hour = value.get_as_vec::<u8>("BYHOUR")?;
-    let by_minute = value.get_as_vec::<u8>("BYMINUTE")?;
-    let by_second = value.get_as_vec::<u8>("BYSECOND")?;
-    let by_month = value.get_as_vec::<Month>("BYMONTH")?;
+    let by_hour = value.get_as_vec::<u8>("BYHOUR")?.unwrap_or_default();
+    let by_minute = value.get_as_vec::<u8>("BYMINUTE")?.unwrap_or_default();
+    let by_second = value.get_as_vec::<u8>("BYSECOND")?.unwrap_or_default();
+    let by_month = value.get_as_vec::<Month>("BYMONTH")?.unwrap_or_default();
     let weekstart = value.get_as::<Weekday>("WKST")?;
-    let by_weekday = value.get_as_vec::<NWeekday>("BYDAY")?;
-    let by_setpos = value.get_as_vec::<i32>("BYSETPOS")?;
-    let by_monthday = value.get_as_vec::<i8>("BYMONTHDAY")?;
-    let by_yearday = value.get_as_vec::<i16>("BYYEARDAY")?;
-    let by_weekno = value.get_as_vec::<i8>("BYWEEKNO")?;
+    let by_weekday = value.get_as_vec::<NWeekday>("BYDAY")?.unwrap_or_default();
+    let by_setpos = value.get_as_vec::<i32>("BYSETPOS")?.unwrap_or_default();
+    let by_monthday = value.get_as_vec::<i8>("BYMONTHDAY")?.unwrap_or_default();
+    let by_yearday = value.get_as_vec::<i16>("BYYEARDAY")?.unwrap_or_default();
+    let by_weekno = value.get_as_vec::<i8>("BYWEEKNO")?.unwrap_or_default();
+
+    verify_range("BYHOUR", &by_hour, 0..=23, false)?;
+    verify_range("BYMINUTE", &by_minute, 0..=59, false)?;
+    verify_range("BYSECOND", &by_second, 0..=60, false)?;
+    verify_range("BYSETPOS", &by_setpos, 1..=366, true)?;
+    verify_range("BYMONTHDAY", &by_monthday, 1..=31, true)?;
+    verify_range("BYYEARDAY", &by_yearday, 1..=366, true)?;
+    verify_range("BYWEEKNO", &by_weekno, 1..=53, true)?;
 
     Ok(Self {
       frequency,
@@ -280,29 +468,119 @@ impl RRule {
       interval,
       count,
       weekstart,
-      by_weekday: by_weekday.unwrap_or_default(),
-      by_hour: by_hour.unwrap_or_default(),
-      by_minute: by_minute.unwrap_or_default(),
-      by_second: by_second.unwrap_or_default(),
-      by_monthday: by_monthday.unwrap_or_default(),
-      by_yearday: by_yearday.unwrap_or_default(),
-      by_setpos: by_setpos.unwrap_or_default(),
-      by_month: by_month.unwrap_or_default(),
-      by_weekno: by_weekno.unwrap_or_default(),
+      by_weekday,
+      by_hour,
+      by_minute,
+      by_second,
+      by_monthday,
+      by_yearday,
+      by_setpos,
+      by_month,
+      by_weekno,
+      unknown_parameters: property.parameters().clone(),
+      unknown_parts: value.without(&PARTS),
     })
   }
+
+  /// Converts the rule into `rrule::RRule`, filling in the BYxxx values that
+  /// are implied by DTSTART. This way the rule keeps producing the same
+  /// occurrences when validated against a start other than DTSTART.
+  fn to_unvalidated_rrule(
+    &self,
+    dtstart: &DtStart,
+    disambiguation: Disambiguation,
+  ) -> Result<rrule::RRule<rrule::Unvalidated>, Error> {
+    let start = dtstart.to_engine(disambiguation)?;
+    let mut rrule = rrule::RRule::new((&self.frequency).into());
+
+    rrule = rrule.interval(self.interval.unwrap_or(1));
+
+    if let Some(count) = self.count {
+      rrule = rrule.count(count);
+    }
+
+    if let Some(until) = &self.until {
+      let timezone = dtstart.derive_timezone();
+      let until = until.to_datetime(&timezone, disambiguation)?;
+      let until = timezone.to_engine(&until).with_timezone(&rrule::Tz::UTC);
+
+      rrule = rrule.until(until);
+    }
+
+    let mut by_month = self
+      .by_month()
+      .iter()
+      .map(|month| month.into())
+      .collect::<Vec<chrono::Month>>();
+    let mut by_monthday = self.by_monthday.clone();
+    let mut by_weekday = self
+      .by_weekday
+      .iter()
+      .map(|day| day.into())
+      .collect::<Vec<rrule::NWeekday>>();
+
+    if self.by_weekno.is_empty()
+      && self.by_yearday.is_empty()
+      && self.by_monthday.is_empty()
+      && self.by_weekday.is_empty()
+    {
+      match self.frequency {
+        Frequency::Yearly => {
+          if by_month.is_empty() {
+            let month = chrono::Month::try_from(start.month() as u8)
+              .map_err(|_| Error::invalid_value("month", &start.month().to_string()))?;
+
+            by_month.push(month);
+          }
+
+          by_monthday.push(start.day() as i8);
+        }
+        Frequency::Monthly => by_monthday.push(start.day() as i8),
+        Frequency::Weekly => by_weekday.push(rrule::NWeekday::Every(start.weekday())),
+        _ => {}
+      }
+    }
+
+    let (by_hour, by_minute, by_second) = match self.frequency {
+      Frequency::Hourly => (
+        Vec::new(),
+        vec![start.minute() as u8],
+        vec![start.second() as u8],
+      ),
+      Frequency::Minutely => (Vec::new(), Vec::new(), vec![start.second() as u8]),
+      Frequency::Secondly => (Vec::new(), Vec::new(), Vec::new()),
+      _ => (
+        vec![start.hour() as u8],
+        vec![start.minute() as u8],
+        vec![start.second() as u8],
+      ),
+    };
+
+    rrule = rrule.by_hour(or_default(&self.by_hour, by_hour));
+    rrule = rrule.by_minute(or_default(&self.by_minute, by_minute));
+    rrule = rrule.by_second(or_default(&self.by_second, by_second));
+    rrule = rrule.by_month_day(by_monthday);
+    rrule = rrule.by_set_pos(self.by_setpos.clone());
+    rrule = rrule.by_month(&by_month);
+    rrule = rrule.by_week_no(self.by_weekno.clone());
+    rrule = rrule.by_year_day(self.by_yearday.clone());
+    rrule = rrule.week_start(self.weekstart.as_ref().unwrap_or_default().into());
+    rrule = rrule.by_weekday(by_weekday);
+
+    Ok(rrule)
+  }
 }
 
 impl TryFrom<Property> for RRule {
-  type Error = String;
+  type Error = Error;
 
   fn try_from(property: Property) -> Result<Self, Self::Error> {
-    Self::from_property(property)
+    Self::from_property(&property)
   }
 }
 
 impl FromStr for RRule {
-  type Err = String;
+  type Err = Error;
 
   fn from_str(str: &str) -> Result<Self, Self::Err> {
     Self::from_str(str)
@@ -310,47 +588,58 @@ impl FromStr for RRule {
 }
 
 pub trait ToRRule {
-  fn to_rrule(&self, tzid: &DtStart) -> Result<rrule::RRule<rrule::Validated>, String>;
+  fn to_rrule(
+    &self,
+    dtstart: &DtStart,
+    disambiguation: Disambiguation,
+  ) -> Result<rrule::RRule<rrule::Validated>, Error>;
+
+  fn to_rrule_from(
+    &self,
+    dtstart: &DtStart,
+    start: &chrono::DateTime<rrule::Tz>,
+    disambiguation: Disambiguation,
+  ) -> Result<Option<rrule::RRule<rrule::Validated>>, Error>;
 }
 
 impl ToRRule for RRule {
-  fn to_rrule(&self, dtstart: &DtStart) -> Result<rrule::RRule<rrule::Validated>, String> {
-    let mut rrule = rrule::RRule::new((&self.frequency).into());
-
-    rrule = rrule.interval(self.interval.unwrap_or(1));
-
-    if let Some(count) = self.count {
-      rrule = rrule.count(count);
-    }
-
-    if let Some(until) = &self.until {
-      let until = until.to_datetime(dtstart.tzid().unwrap_or(&chrono_tz::Tz::UTC))?;
-      let until = until.with_timezone(&rrule::Tz::Tz(chrono_tz::Tz::UTC));
-
-      rrule = rrule.until(until);
+  fn to_rrule(
+    &self,
+    dtstart: &DtStart,
+    disambiguation: Disambiguation,
+  ) -> Result<rrule::RRule<rrule::Validated>, Error> {
+    let rrule = self.to_unvalidated_rrule(dtstart, disambiguation)?;
+
+    let dtstart = dtstart.to_engine(disambiguation)?;
+
+    rrule
+      .validate(dtstart)
+      .map_err(|err| Error::Validation(err.to_string()))
+  }
+
+  /// Builds the rule so that it can be iterated from `start` instead of DTSTART.
+  ///
+  /// Returns `None` if the rule ends before `start`. `start` must be aligned
+  /// with the rule (see `RRule::align`), otherwise the produced occurrences
+  /// will differ from the ones produced from DTSTART.
+  fn to_rrule_from(
+    &self,
+    dtstart: &DtStart,
+    start: &chrono::DateTime<rrule::Tz>,
+    disambiguation: Disambiguation,
+  ) -> Result<Option<rrule::RRule<rrule::Validated>>, Error> {
+    let rrule = self.to_unvalidated_rrule(dtstart, disambiguation)?;
+
+    if let Some(until) = rrule.get_until() {
+      if until < start {
+        return Ok(None);
+      }
     }
 
-    rrule = rrule.by_hour(self.by_hour.clone());
-    rrule = rrule.by_minute(self.by_minute.clone());
-    rrule = rrule.by_second(self.by_second.clone());
-    rrule = rrule.by_month_day(self.by_monthday.clone());
-    rrule = rrule.by_set_pos(self.by_setpos.clone());
-    rrule = rrule.by_month(
-      &self
-        .by_month()
-        .iter()
-        .map(|month| month.into())
-        .collect::<Vec<chrono::Month>>(),
-    );
-    rrule = rrule.by_week_no(self.by_weekno.clone());
-    rrule = rrule.by_year_day(self.by_yearday.clone());
-    rrule = rrule.week_start(self.weekstart.as_ref().unwrap_or_default().into());
-    rrule = rrule.by_weekday(self.by_weekday.iter().map(|day| day.into()).collect());
-
-    let dtstart = dtstart.to_datetime()?;
-    let dtstart = dtstart.with_timezone(&rrule::Tz::Tz(dtstart.timezone()));
-
-    rrule.validate(dtstart).map_err(|err| format!("{}", err))
+    rrule
+      .validate(*start)
+      .map(Some)
+      .map_err(|err| Error::Validation(err.to_string()))
   }
 }
 
@@ -359,3 +648,30 @@ impl fmt::Display for RRule {
     write!(f, "{}", self.to_property())
   }
 }
+
+fn or_default<T: Clone>(values: &[T], default: Vec<T>) -> Vec<T> {
+  if values.is_empty() {
+    default
+  } else {
+    values.to_vec()
+  }
+}
+
+/// Checks that the values of the `name` rule part lie in `range`, or in its
+/// negation if the part counts from the end of the period when `signed`.
+fn verify_range<T: Copy + Into<i32>>(
+  name: &str,
+  values: &[T],
+  range: RangeInclusive<i32>,
+  signed: bool,
+) -> Result<(), Error> {
+  for value in values.iter().map(|value| (*value).into()) {
+    let magnitude = if signed { value.abs() } else { value };
+
+    if !range.contains(&magnitude) {
+      return Err(Error::invalid_value(name, &value.to_string()));
+    }
+  }
+
+  Ok(())
+}
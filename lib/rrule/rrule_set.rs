@@ -1,16 +1,30 @@
-use std::{fmt, str::FromStr};
+use std::{fmt, iter::Peekable, str::FromStr, sync::Arc};
 
-use crate::{rrule::value_type::ValueType, serialization::properties::Properties};
+use chrono::{Datelike, Duration, TimeZone};
+
+use crate::{
+  error::Error,
+  rrule::value_type::ValueType,
+  serialization::{properties::Properties, property::Property},
+};
 
 use super::{
   calendar::Calendar,
   datetime::DateTime,
+  disambiguation::Disambiguation,
   dtstart::DtStart,
   exdate::ExDate,
+  extent::Extent,
+  preserve::Preserve,
   rdate::RDate,
   rrule::{RRule, ToRRule},
+  timezone::{CustomTimezone, Timezone},
 };
 
+/// Maximum number of alignment rounds performed while seeking before falling
+/// back to iterating from DTSTART.
+const MAX_SEEK_ROUNDS: usize = 1000;
+
 #[derive(Clone)]
 pub struct RRuleSet {
   dtstart: DtStart,
@@ -18,6 +32,10 @@ pub struct RRuleSet {
   exrules: Vec<RRule>,
   exdates: Vec<ExDate>,
   rdates: Vec<RDate>,
+  /// Properties other than DTSTART, RRULE, EXRULE, EXDATE and RDATE, kept as
+  /// parsed.
+  unknown_properties: Vec<Property>,
+  disambiguation: Disambiguation,
 }
 
 impl RRuleSet {
@@ -28,6 +46,8 @@ impl RRuleSet {
       exrules: Vec::new(),
       exdates: Vec::new(),
       rdates: Vec::new(),
+      unknown_properties: Vec::new(),
+      disambiguation: Disambiguation::default(),
     }
   }
 
@@ -51,23 +71,47 @@ impl RRuleSet {
     &self.rdates
   }
 
-  pub fn set_rrules(self, rrules: Vec<RRule>) -> Result<Self, String> {
+  pub fn unknown_properties(&self) -> &Vec<Property> {
+    &self.unknown_properties
+  }
+
+  pub fn disambiguation(&self) -> Disambiguation {
+    self.disambiguation
+  }
+
+  /// Sets how wall-clock times in a DST gap or overlap are resolved, both the
+  /// ones of DTSTART, UNTIL, EXDATE and RDATE and the generated ones.
+  pub fn set_disambiguation(self, disambiguation: Disambiguation) -> Self {
+    Self {
+      disambiguation,
+      ..self
+    }
+  }
+
+  pub fn set_unknown_properties(self, unknown_properties: Vec<Property>) -> Self {
+    Self {
+      unknown_properties,
+      ..self
+    }
+  }
+
+  pub fn set_rrules(self, rrules: Vec<RRule>) -> Result<Self, Error> {
     self.verify_rrules(&rrules)?;
 
     Ok(Self { rrules, ..self })
   }
 
-  pub fn set_exrules(self, exrules: Vec<RRule>) -> Result<Self, String> {
+  pub fn set_exrules(self, exrules: Vec<RRule>) -> Result<Self, Error> {
     self.verify_rrules(&exrules)?;
 
     Ok(Self { exrules, ..self })
   }
 
-  pub fn set_exdates(self, exdates: Vec<ExDate>) -> Result<Self, String> {
+  pub fn set_exdates(self, exdates: Vec<ExDate>) -> Result<Self, Error> {
     for exdate in exdates.iter() {
       if let Some(vt) = exdate.derive_value_type() {
         if self.dtstart().derive_value_type() != vt {
-          return Err("EXDATE value type does not match DTSTART value type".to_string());
+          return Err(Error::DtStartValueTypeMismatch("EXDATE".to_string()));
         }
       }
     }
@@ -75,11 +119,11 @@ impl RRuleSet {
     Ok(Self { exdates, ..self })
   }
 
-  pub fn set_rdates(self, rdates: Vec<RDate>) -> Result<Self, String> {
+  pub fn set_rdates(self, rdates: Vec<RDate>) -> Result<Self, Error> {
     for rdate in rdates.iter() {
       if let Some(vt) = rdate.derive_value_type() {
         if self.dtstart().derive_value_type() != vt {
-          return Err("RDATE value type does not match DTSTART value type".to_string());
+          return Err(Error::DtStartValueTypeMismatch("RDATE".to_string()));
         }
       }
     }
@@ -87,13 +131,19 @@ impl RRuleSet {
     Ok(Self { rdates, ..self })
   }
 
-  pub fn set_from_string(mut self, str: &str) -> Result<Self, String> {
+  pub fn set_from_string(mut self, str: &str) -> Result<Self, Error> {
     let calendar: Calendar = str.parse()?;
-    let (calendar_dtstarts, calendar_rrules, calendar_exrules, calendar_exdates, calendar_rdates) =
-      calendar.into();
+    let (
+      calendar_dtstarts,
+      calendar_rrules,
+      calendar_exrules,
+      calendar_exdates,
+      calendar_rdates,
+      calendar_unknown_properties,
+    ) = calendar.into();
 
     if calendar_dtstarts.len() > 1 {
-      return Err("Only one DTSTART is allowed".to_string());
+      return Err(Error::DuplicateProperty("DTSTART".to_string()));
     }
 
     if let Some(dtstart) = calendar_dtstarts.first() {
@@ -116,9 +166,19 @@ impl RRuleSet {
       self.rdates.push(rdate);
     }
 
+    for property in calendar_unknown_properties {
+      self.unknown_properties.push(property);
+    }
+
     Ok(self)
   }
 
+  /// Same as `to_string`, but folds the content lines longer than 75 octets as
+  /// defined by RFC 5545.
+  pub fn to_folded_string(&self) -> String {
+    self.to_properties().to_folded_string()
+  }
+
   pub fn to_properties(&self) -> Properties {
     let mut properties = Properties::new();
 
@@ -140,23 +200,262 @@ impl RRuleSet {
       properties.push(rdate.to_property());
     }
 
+    for property in self.unknown_properties.iter() {
+      properties.push(property.clone());
+    }
+
     properties
   }
 
-  pub fn iterator(&self) -> Result<RRuleSetIterator, String> {
+  pub fn iterator(&self) -> Result<RRuleSetIterator, Error> {
     Ok(RRuleSetIterator {
       value_type: self.dtstart.derive_value_type(),
-      iter: self.to_rrule_set()?.into_iter(),
+      iter: self.occurrences(self.to_rrule_set()?)?.peekable(),
+      timezone: None,
     })
   }
 
-  pub fn from_str(str: &str) -> Result<Self, String> {
-    let calendar: Calendar = str.parse()?;
-    let (calendar_dtstarts, calendar_rrules, calendar_exrules, calendar_exdates, calendar_rdates) =
-      calendar.into();
+  /// Returns an iterator over the occurrences converted into `timezone`,
+  /// keeping their instants.
+  pub fn iterator_in(&self, timezone: &Timezone) -> Result<RRuleSetIterator, Error> {
+    self.require_instants()?;
+
+    Ok(self.iterator()?.in_timezone(timezone.clone()))
+  }
+
+  /// Returns an iterator over the occurrences at or after `datetime`.
+  ///
+  /// Instead of enumerating every occurrence since DTSTART, the iteration
+  /// starts close to `datetime` (see `RRuleSet::seek`).
+  pub fn iterator_from(
+    &self,
+    datetime: &DateTime,
+    inclusive: bool,
+  ) -> Result<RRuleSetIterator, Error> {
+    let datetime = datetime.to_datetime(&self.dtstart.derive_timezone(), self.disambiguation)?;
+    let mut iter = self
+      .occurrences(self.to_rrule_set_from(&datetime)?)?
+      .peekable();
+
+    while iter
+      .next_if(|occurrence| {
+        if inclusive {
+          *occurrence < datetime
+        } else {
+          *occurrence <= datetime
+        }
+      })
+      .is_some()
+    {}
+
+    Ok(RRuleSetIterator {
+      value_type: self.dtstart.derive_value_type(),
+      iter,
+      timezone: None,
+    })
+  }
+
+  /// Returns the first occurrence after `datetime`.
+  pub fn after(&self, datetime: &DateTime, inclusive: bool) -> Result<Option<DateTime>, Error> {
+    Ok(self.iterator_from(datetime, inclusive)?.next())
+  }
+
+  /// Returns whether `datetime` is an occurrence of the set.
+  pub fn includes(&self, datetime: &DateTime) -> Result<bool, Error> {
+    let target = datetime.to_datetime(&self.dtstart.derive_timezone(), self.disambiguation)?;
+    let mut iter = self.iterator_from(datetime, true)?;
+
+    Ok(iter.next_before(&target, true).is_some())
+  }
+
+  /// Returns the occurrences between `after` and `before`.
+  pub fn between(
+    &self,
+    after: &DateTime,
+    before: &DateTime,
+    inclusive: bool,
+  ) -> Result<Vec<DateTime>, Error> {
+    let before = before.to_datetime(&self.dtstart.derive_timezone(), self.disambiguation)?;
+    let mut iter = self.iterator_from(after, inclusive)?;
+
+    Ok(std::iter::from_fn(|| iter.next_before(&before, inclusive)).collect())
+  }
+
+  /// Returns the occurrences between `after` and `before`, converted into
+  /// `timezone`.
+  pub fn between_in(
+    &self,
+    after: &DateTime,
+    before: &DateTime,
+    inclusive: bool,
+    timezone: &Timezone,
+  ) -> Result<Vec<DateTime>, Error> {
+    self.require_instants()?;
+
+    let before = before.to_datetime(&self.dtstart.derive_timezone(), self.disambiguation)?;
+    let mut iter = self
+      .iterator_from(after, inclusive)?
+      .in_timezone(timezone.clone());
+
+    Ok(std::iter::from_fn(|| iter.next_before(&before, inclusive)).collect())
+  }
+
+  /// Returns an iterator over the occurrences as UTC epoch milliseconds.
+  /// Dates stand for midnight in the time zone of the set.
+  pub fn timestamps(&self) -> Result<impl Iterator<Item = i64>, Error> {
+    self.require_instants()?;
+
+    Ok(
+      self
+        .iterator()?
+        .iter
+        .map(|occurrence| occurrence.timestamp_millis()),
+    )
+  }
+
+  /// Returns the occurrences between `after` and `before` as UTC epoch
+  /// milliseconds.
+  pub fn between_timestamps(
+    &self,
+    after: &DateTime,
+    before: &DateTime,
+    inclusive: bool,
+  ) -> Result<Vec<i64>, Error> {
+    self.require_instants()?;
+
+    let before = before.to_datetime(&self.dtstart.derive_timezone(), self.disambiguation)?;
+
+    Ok(
+      self
+        .iterator_from(after, inclusive)?
+        .iter
+        .take_while(|occurrence| {
+          if inclusive {
+            *occurrence <= before
+          } else {
+            *occurrence < before
+          }
+        })
+        .map(|occurrence| occurrence.timestamp_millis())
+        .collect(),
+    )
+  }
+
+  /// Returns the number of occurrences of a finite set.
+  ///
+  /// Sets without exclusions whose rules are limited by COUNT alone and
+  /// repeat DTSTART every period of a fixed length are counted without
+  /// iterating them.
+  pub fn count(&self) -> Result<usize, Error> {
+    if !self.is_finite() {
+      return Err(Error::InfiniteSet);
+    }
+
+    if let Some(count) = self.counted()? {
+      return Ok(count);
+    }
+
+    Ok(self.iterator()?.count())
+  }
+
+  /// Returns whether the set is finite, along with its last occurrence if it
+  /// is.
+  ///
+  /// The last occurrence is looked up backwards from the end of the set as
+  /// `RRuleSet::before` does, unless a rule limited by COUNT needs to be
+  /// iterated to find its end.
+  pub fn extent(&self) -> Result<Extent, Error> {
+    if !self.is_finite() {
+      return Ok(Extent::Infinite);
+    }
+
+    Ok(Extent::Finite { last: self.last()? })
+  }
+
+  /// Returns the number of occurrences between `after` and `before`.
+  pub fn count_between(
+    &self,
+    after: &DateTime,
+    before: &DateTime,
+    inclusive: bool,
+  ) -> Result<usize, Error> {
+    let before = before.to_datetime(&self.dtstart.derive_timezone(), self.disambiguation)?;
+    let mut iter = self.iterator_from(after, inclusive)?;
+
+    Ok(std::iter::from_fn(|| iter.next_before(&before, inclusive)).count())
+  }
+
+  /// Returns the occurrence at zero-based `index`.
+  pub fn nth(&self, index: usize) -> Result<Option<DateTime>, Error> {
+    Ok(self.iterator()?.nth(index))
+  }
+
+  /// Returns the zero-based index of `datetime` among the occurrences, or
+  /// `None` if it is not an occurrence of the set.
+  pub fn index_of(&self, datetime: &DateTime) -> Result<Option<usize>, Error> {
+    if !self.includes(datetime)? {
+      return Ok(None);
+    }
+
+    let datetime = datetime.to_datetime(&self.dtstart.derive_timezone(), self.disambiguation)?;
+    let mut iter = self.iterator()?;
+
+    Ok(Some(
+      std::iter::from_fn(|| iter.next_before(&datetime, false)).count(),
+    ))
+  }
+
+  /// Returns an iterator over the occurrences at or before `datetime` in
+  /// descending order, down to the start of the set.
+  ///
+  /// The set is iterated in windows preceding `datetime`. A window is doubled
+  /// whenever it contains no occurrence, so sparse sets are walked quickly.
+  /// An error iterating a window is yielded, and ends the iteration.
+  pub fn reverse_iterator_from(
+    &self,
+    datetime: &DateTime,
+    inclusive: bool,
+  ) -> Result<RRuleSetReverseIterator<'_>, Error> {
+    let datetime = datetime.to_datetime(&self.dtstart.derive_timezone(), self.disambiguation)?;
+
+    // Surfaces conversion errors up front, before any window is iterated
+    self.occurrences(self.to_rrule_set()?)?;
+
+    Ok(RRuleSetReverseIterator {
+      rrule_set: self,
+      value_type: self.dtstart.derive_value_type(),
+      lower_bound: self.lower_bound()?,
+      upper_bound: Some(datetime),
+      inclusive,
+      window: self.window(),
+      occurrences: Vec::new(),
+    })
+  }
+
+  /// Returns the last occurrence before `datetime`.
+  pub fn before(&self, datetime: &DateTime, inclusive: bool) -> Result<Option<DateTime>, Error> {
+    self
+      .reverse_iterator_from(datetime, inclusive)?
+      .next()
+      .transpose()
+  }
+
+  pub fn from_str(str: &str) -> Result<Self, Error> {
+    Self::from_calendar(str.parse()?)
+  }
+
+  pub(super) fn from_calendar(calendar: Calendar) -> Result<Self, Error> {
+    let (
+      calendar_dtstarts,
+      calendar_rrules,
+      calendar_exrules,
+      calendar_exdates,
+      calendar_rdates,
+      calendar_unknown_properties,
+    ) = calendar.into();
 
     if calendar_dtstarts.len() > 1 {
-      return Err("Only one DTSTART is allowed".to_string());
+      return Err(Error::DuplicateProperty("DTSTART".to_string()));
     }
 
     let dtstart: Option<DtStart> = calendar_dtstarts.into_iter().nth(0);
@@ -168,7 +467,7 @@ impl RRuleSet {
     let dtstart = match dtstart {
       Some(value) => value,
       None => {
-        return Err("DTSTART is required".to_string());
+        return Err(Error::MissingProperty("DTSTART".to_string()));
       }
     };
 
@@ -193,23 +492,388 @@ impl RRuleSet {
       .set_rdates(rdates)?
       .set_rrules(rrules)?
       .set_exrules(exrules)
+      .map(|set| set.set_unknown_properties(calendar_unknown_properties))
   }
 
-  fn verify_rrules(&self, rrules: &[RRule]) -> Result<(), String> {
+  fn verify_rrules(&self, rrules: &[RRule]) -> Result<(), Error> {
     for rrule in rrules.iter() {
       if let Some(until) = rrule.until() {
         if until.derive_value_type() != self.dtstart().derive_value_type() {
-          return Err("RRULE UNTIL value type does not match DTSTART value type".to_string());
+          return Err(Error::DtStartValueTypeMismatch("UNTIL".to_string()));
         }
       }
     }
 
     Ok(())
   }
+
+  /// Finds a moment at or before `datetime` from which the set can be iterated
+  /// instead of DTSTART without changing the produced occurrences.
+  ///
+  /// Every rule iterates over periods (years, months, weeks...) whose distance
+  /// from the DTSTART period is a multiple of INTERVAL. Starting from
+  /// `datetime`, the candidate is moved back until it falls into such a period
+  /// for every RRULE and EXRULE at once. Returns `None` if the set has to be
+  /// iterated from DTSTART, e.g. because a rule is limited by COUNT.
+  fn seek(
+    &self,
+    datetime: &chrono::DateTime<Timezone>,
+  ) -> Result<Option<chrono::DateTime<rrule::Tz>>, Error> {
+    let dtstart = self.dtstart.to_datetime(self.disambiguation)?;
+    let timezone = dtstart.timezone();
+    let rules = || self.rrules.iter().chain(self.exrules.iter());
+
+    if *datetime <= dtstart {
+      return Ok(None);
+    }
+
+    let origin = self.dtstart.to_engine(self.disambiguation)?.naive_utc();
+    // Occurrences in a DST gap before `datetime` may be moved past it, so the
+    // search starts at the earliest wall-clock time resolving to it
+    let mut candidate = Disambiguation::earliest_local(&timezone, &datetime.naive_utc());
+
+    for _ in 0..MAX_SEEK_ROUNDS {
+      let aligned = rules().try_fold(candidate, |aligned, rule| {
+        rule
+          .align(&origin, candidate)
+          .map(|datetime| datetime.min(aligned))
+      });
+
+      match aligned {
+        Some(aligned) if aligned <= origin => break,
+        Some(aligned) if aligned < candidate => candidate = aligned,
+        Some(_) => return Ok(Some(rrule::Tz::UTC.from_utc_datetime(&candidate))),
+        None => break,
+      }
+    }
+
+    Ok(None)
+  }
+
+  /// Converts the set into `rrule::RRuleSet` that starts iterating at or
+  /// before `datetime`.
+  fn to_rrule_set_from(
+    &self,
+    datetime: &chrono::DateTime<Timezone>,
+  ) -> Result<rrule::RRuleSet, Error> {
+    let start = match self.seek(datetime)? {
+      Some(start) => start,
+      None => return self.to_rrule_set(),
+    };
+    let mut rrule_set = rrule::RRuleSet::new(start);
+
+    for rrule in self.rrules.iter() {
+      if let Some(rrule) = rrule.to_rrule_from(self.dtstart(), &start, self.disambiguation)? {
+        rrule_set = rrule_set.rrule(rrule);
+      }
+    }
+
+    for exrule in self.exrules.iter() {
+      if let Some(exrule) = exrule.to_rrule_from(self.dtstart(), &start, self.disambiguation)? {
+        rrule_set = rrule_set.exrule(exrule);
+      }
+    }
+
+    Ok(rrule_set)
+  }
+
+  /// Binds a floating set to `timezone`: the floating DTSTART, EXDATE and
+  /// RDATE values keep their wall-clock time in it, while floating UNTIL
+  /// values are converted into UTC. Sets which aren't floating are returned
+  /// as is.
+  pub fn resolve_floating(self, timezone: &Timezone) -> Result<Self, Error> {
+    if !self.dtstart.is_floating() {
+      return Ok(self);
+    }
+
+    let resolve_rules = |rules: Vec<RRule>| {
+      rules
+        .into_iter()
+        .map(|rule| rule.resolve_floating(timezone))
+        .collect::<Result<Vec<RRule>, Error>>()
+    };
+
+    Ok(Self {
+      dtstart: self.dtstart.resolve_floating(timezone),
+      rrules: resolve_rules(self.rrules)?,
+      exrules: resolve_rules(self.exrules)?,
+      exdates: self
+        .exdates
+        .into_iter()
+        .map(|exdate| exdate.resolve_floating(timezone))
+        .collect(),
+      rdates: self
+        .rdates
+        .into_iter()
+        .map(|rdate| rdate.resolve_floating(timezone))
+        .collect(),
+      unknown_properties: self.unknown_properties,
+      disambiguation: self.disambiguation,
+    })
+  }
+
+  /// Moves the set into `timezone`, preserving either the instants or the
+  /// wall-clock times of its occurrences. DTSTART, EXDATE and RDATE values
+  /// become wall-clock times of `timezone`, UNTIL values are written in UTC.
+  /// The rules are iterated in the wall-clock time of `timezone` afterwards,
+  /// so kept instants only hold up to DST transitions the time zones differ
+  /// in.
+  ///
+  /// Sets of dates are returned as is. Floating sets are bound to no instant,
+  /// so they can only keep their wall-clock times.
+  pub fn with_timezone(self, timezone: &Timezone, preserve: Preserve) -> Result<Self, Error> {
+    if self.dtstart.derive_value_type() == ValueType::Date {
+      return Ok(self);
+    }
+
+    if preserve == Preserve::Instant && self.dtstart.is_floating() {
+      return Err(Error::MissingTimezone("DTSTART".to_string()));
+    }
+
+    let origin = self.dtstart.derive_timezone();
+    let disambiguation = self.disambiguation;
+    let move_rules = |rules: Vec<RRule>| {
+      rules
+        .into_iter()
+        .map(|rule| rule.with_timezone(&origin, timezone, preserve, disambiguation))
+        .collect::<Result<Vec<RRule>, Error>>()
+    };
+
+    Ok(Self {
+      rrules: move_rules(self.rrules)?,
+      exrules: move_rules(self.exrules)?,
+      exdates: self
+        .exdates
+        .into_iter()
+        .map(|exdate| exdate.with_timezone(&origin, timezone, preserve, disambiguation))
+        .collect::<Result<Vec<ExDate>, Error>>()?,
+      rdates: self
+        .rdates
+        .into_iter()
+        .map(|rdate| rdate.with_timezone(&origin, timezone, preserve, disambiguation))
+        .collect::<Result<Vec<RDate>, Error>>()?,
+      dtstart: self
+        .dtstart
+        .with_timezone(timezone, preserve, disambiguation)?,
+      unknown_properties: self.unknown_properties,
+      disambiguation,
+    })
+  }
+
+  /// Fails for floating sets. Their occurrences are bound to no instant, so
+  /// they can't be converted until the set is resolved by
+  /// `RRuleSet::resolve_floating`.
+  fn require_instants(&self) -> Result<(), Error> {
+    if self.dtstart.is_floating() {
+      return Err(Error::MissingTimezone("DTSTART".to_string()));
+    }
+
+    Ok(())
+  }
+
+  /// Returns the distinct time zones named by the TZID parameters of DTSTART,
+  /// EXDATE and RDATE.
+  pub fn timezones(&self) -> Vec<Timezone> {
+    let tzids = std::iter::once(self.dtstart.tzid())
+      .chain(self.exdates.iter().map(|exdate| exdate.tzid().as_ref()))
+      .chain(self.rdates.iter().map(|rdate| rdate.tzid().as_ref()));
+    let mut timezones: Vec<Timezone> = Vec::new();
+
+    for timezone in tzids.flatten() {
+      if !timezones.contains(timezone) {
+        timezones.push(timezone.clone());
+      }
+    }
+
+    timezones
+  }
+
+  /// Returns the custom time zones of DTSTART, EXDATE and RDATE, without
+  /// duplicates.
+  pub fn custom_timezones(&self) -> Vec<Arc<CustomTimezone>> {
+    self
+      .timezones()
+      .iter()
+      .filter_map(Timezone::custom)
+      .cloned()
+      .collect()
+  }
+
+  /// Returns whether the set produces a finite number of occurrences, i.e.
+  /// every RRULE is limited by COUNT or UNTIL.
+  pub fn is_finite(&self) -> bool {
+    self
+      .rrules
+      .iter()
+      .all(|rrule| rrule.count().is_some() || rrule.until().is_some())
+  }
+
+  /// Returns the number of occurrences of a finite set without iterating it,
+  /// see `RRuleSet::count`. Returns `None` if it has to be iterated.
+  fn counted(&self) -> Result<Option<usize>, Error> {
+    if !self.exrules.is_empty() || !self.exdates.is_empty() {
+      return Ok(None);
+    }
+
+    // Surfaces the errors iterating the set would raise
+    self.occurrences(self.to_rrule_set()?)?;
+
+    let origin = self.dtstart.to_engine(self.disambiguation)?.naive_utc();
+    let mut count: usize = self.rdates.iter().map(|rdate| rdate.values().len()).sum();
+
+    for rrule in self.rrules.iter() {
+      match (rrule.count(), rrule.last_counted(&origin)) {
+        (Some(rrule_count), Some(_)) => count += rrule_count as usize,
+        _ => return Ok(None),
+      }
+    }
+
+    Ok(Some(count))
+  }
+
+  /// Returns the last occurrence of a finite set, see `RRuleSet::extent`.
+  fn last(&self) -> Result<Option<DateTime>, Error> {
+    let timezone = self.dtstart.derive_timezone();
+    let origin = self.dtstart.to_engine(self.disambiguation)?.naive_utc();
+    // Occurrences known to be part of the set unless excluded, and bounds no
+    // occurrence of a rule is past
+    let mut occurrences = Vec::new();
+    let mut bounds = Vec::new();
+
+    for rdate in self.rdates.iter() {
+      occurrences.extend(rdate.to_datetimes(&self.dtstart, self.disambiguation)?);
+    }
+
+    for rrule in self.rrules.iter() {
+      let last = rrule
+        .last_counted(&origin)
+        .and_then(|last| self.disambiguation.resolve_occurrence(&timezone, &last));
+
+      match (rrule.until(), last) {
+        (Some(until), _) => bounds.push(until.to_datetime(&timezone, self.disambiguation)?),
+        (None, Some(last)) => occurrences.push(last),
+        (None, None) => return Ok(self.iterator()?.last()),
+      }
+    }
+
+    let excluded = !self.exrules.is_empty() || !self.exdates.is_empty();
+    let latest = occurrences.into_iter().max();
+    let bound = bounds.into_iter().max();
+
+    match (latest, bound) {
+      (Some(latest), bound) if !excluded && bound.as_ref().is_none_or(|bound| latest >= *bound) => {
+        // Surfaces the errors iterating the set would raise
+        self.occurrences(self.to_rrule_set()?)?;
+
+        Ok(Some(to_occurrence(
+          &latest,
+          &self.dtstart.derive_value_type(),
+          None,
+        )))
+      }
+      (latest, bound) => match latest.into_iter().chain(bound).max() {
+        Some(upper_bound) => self.before(&(&upper_bound).into(), true),
+        None => Ok(None),
+      },
+    }
+  }
+
+  /// Returns the year no occurrence of the set is past, read from the RDATEs
+  /// and the UNTIL or COUNT of the RRULEs without iterating the set. Returns
+  /// `None` if it can't be told, e.g. for infinite sets.
+  pub(super) fn last_year(&self) -> Result<Option<i32>, Error> {
+    let timezone = self.dtstart.derive_timezone();
+    let origin = self.dtstart.to_engine(self.disambiguation)?.naive_utc();
+    let mut last_year = self.dtstart.to_datetime(self.disambiguation)?.year();
+
+    for rdate in self.rdates.iter() {
+      for datetime in rdate.to_datetimes(&self.dtstart, self.disambiguation)? {
+        last_year = last_year.max(datetime.year());
+      }
+    }
+
+    for rrule in self.rrules.iter() {
+      let last = match rrule.until() {
+        Some(until) => Some(until.to_datetime(&timezone, self.disambiguation)?),
+        None => rrule
+          .last_counted(&origin)
+          .and_then(|last| self.disambiguation.resolve_occurrence(&timezone, &last)),
+      };
+
+      match last {
+        Some(last) => last_year = last_year.max(last.year()),
+        None => return Ok(None),
+      }
+    }
+
+    Ok(Some(last_year))
+  }
+
+  /// Returns the earliest moment the set can produce an occurrence at.
+  fn lower_bound(&self) -> Result<chrono::DateTime<Timezone>, Error> {
+    let timezone = self.dtstart.derive_timezone();
+    let dtstart = self.dtstart.to_engine(self.disambiguation)?;
+    // Occurrences in a DST gap right after DTSTART may be moved before it
+    let mut lower_bound = timezone.from_utc_datetime(&Disambiguation::earliest_instant(
+      &timezone,
+      &dtstart.naive_utc(),
+    ));
+
+    for rdate in self.rdates.iter() {
+      for datetime in rdate.to_datetimes(&self.dtstart, self.disambiguation)? {
+        lower_bound = lower_bound.min(datetime);
+      }
+    }
+
+    Ok(lower_bound)
+  }
+
+  /// Returns the length of the longest period iterated by the RRULEs, which is
+  /// used as the initial window when looking for preceding occurrences.
+  fn window(&self) -> Duration {
+    self
+      .rrules
+      .iter()
+      .filter_map(|rrule| {
+        rrule
+          .frequency()
+          .max_period_duration()
+          .checked_mul(i32::from(rrule.interval().unwrap_or(1)))
+      })
+      .max()
+      .unwrap_or(Duration::days(1))
+  }
+
+  /// Returns the occurrences of the rules of `rrule_set` in the time zone of
+  /// DTSTART, along with the RDATEs and without the EXDATEs.
+  fn occurrences(&self, rrule_set: rrule::RRuleSet) -> Result<Occurrences, Error> {
+    let mut exdates = Vec::new();
+    let mut rdates = Vec::new();
+
+    for exdate in self.exdates.iter() {
+      exdates.extend(exdate.to_datetimes(&self.dtstart, self.disambiguation)?);
+    }
+
+    for rdate in self.rdates.iter() {
+      rdates.extend(rdate.to_datetimes(&self.dtstart, self.disambiguation)?);
+    }
+
+    exdates.sort();
+    rdates.sort_by(|a, b| b.cmp(a));
+
+    Ok(Occurrences {
+      iter: rrule_set.into_iter().peekable(),
+      timezone: self.dtstart.derive_timezone(),
+      disambiguation: self.disambiguation,
+      pending: Vec::new(),
+      rdates,
+      exdates,
+    })
+  }
 }
 
 impl FromStr for RRuleSet {
-  type Err = String;
+  type Err = Error;
 
   fn from_str(str: &str) -> Result<Self, Self::Err> {
     Self::from_str(str)
@@ -217,47 +881,133 @@ impl FromStr for RRuleSet {
 }
 
 pub trait ToRRuleSet {
-  fn to_rrule_set(&self) -> Result<rrule::RRuleSet, String>;
+  fn to_rrule_set(&self) -> Result<rrule::RRuleSet, Error>;
 }
 
 impl ToRRuleSet for RRuleSet {
-  fn to_rrule_set(&self) -> Result<rrule::RRuleSet, String> {
-    let dtstart = self.dtstart.to_datetime()?;
-    let dtstart = dtstart.with_timezone(&rrule::Tz::Tz(dtstart.timezone()));
+  fn to_rrule_set(&self) -> Result<rrule::RRuleSet, Error> {
+    let dtstart = self.dtstart.to_engine(self.disambiguation)?;
 
     let mut rrule_set = rrule::RRuleSet::new(dtstart);
 
     for rrule in self.rrules.iter() {
-      rrule_set = rrule_set.rrule(rrule.to_rrule(self.dtstart())?);
+      rrule_set = rrule_set.rrule(rrule.to_rrule(self.dtstart(), self.disambiguation)?);
     }
 
     for exrule in self.exrules.iter() {
-      rrule_set = rrule_set.exrule(exrule.to_rrule(self.dtstart())?);
+      rrule_set = rrule_set.exrule(exrule.to_rrule(self.dtstart(), self.disambiguation)?);
     }
 
-    for exdate in self.exdates.iter() {
-      let datetimes = exdate.to_datetimes(&self.dtstart)?;
+    Ok(rrule_set)
+  }
+}
 
-      for datetime in datetimes.into_iter() {
-        rrule_set = rrule_set.exdate(datetime.with_timezone(&rrule::Tz::Tz(datetime.timezone())));
+/// Occurrences of the set in the time zone of DTSTART, in ascending order and
+/// without duplicates.
+///
+/// The recurrence engine produces the wall-clock times of the rules, which are
+/// resolved by the disambiguation policy. Resolving a time in a DST gap can
+/// move it past the following ones, so occurrences are only released once no
+/// time the engine is yet to produce can resolve before them.
+pub struct Occurrences {
+  iter: Peekable<rrule::RRuleSetIter>,
+  timezone: Timezone,
+  disambiguation: Disambiguation,
+  /// Resolved occurrences of the rules which aren't released yet, in
+  /// descending order.
+  pending: Vec<chrono::DateTime<Timezone>>,
+  /// RDATEs which aren't released yet, in descending order.
+  rdates: Vec<chrono::DateTime<Timezone>>,
+  /// EXDATEs in ascending order.
+  exdates: Vec<chrono::DateTime<Timezone>>,
+}
+
+impl Occurrences {
+  /// Returns the earliest pending occurrence or RDATE once none of the
+  /// occurrences the engine is yet to produce can precede it.
+  fn next_ordered(&mut self) -> Option<chrono::DateTime<Timezone>> {
+    loop {
+      let horizon = self
+        .iter
+        .peek()
+        .map(|next| Disambiguation::earliest_instant(&self.timezone, &next.naive_utc()));
+      let earliest = match (self.pending.last(), self.rdates.last()) {
+        (Some(occurrence), Some(rdate)) if rdate < occurrence => &mut self.rdates,
+        (Some(_), _) => &mut self.pending,
+        (None, _) => &mut self.rdates,
+      };
+
+      match (earliest.last(), horizon) {
+        (Some(datetime), Some(horizon)) if datetime.naive_utc() <= horizon => {
+          return earliest.pop()
+        }
+        (Some(_), None) => return earliest.pop(),
+        (None, None) => return None,
+        _ => {}
       }
-    }
 
-    for rdate in self.rdates.iter() {
-      let datetimes = rdate.to_datetimes(&self.dtstart)?;
+      let occurrence = self
+        .iter
+        .next()
+        .and_then(|occurrence| self.timezone.localize(&occurrence, self.disambiguation));
 
-      for datetime in datetimes.into_iter() {
-        rrule_set = rrule_set.rdate(datetime.with_timezone(&rrule::Tz::Tz(datetime.timezone())));
+      if let Some(occurrence) = occurrence {
+        let index = self
+          .pending
+          .partition_point(|pending| *pending > occurrence);
+
+        self.pending.insert(index, occurrence);
       }
     }
+  }
+}
 
-    Ok(rrule_set)
+impl Iterator for Occurrences {
+  type Item = chrono::DateTime<Timezone>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    loop {
+      let occurrence = self.next_ordered()?;
+
+      if self.exdates.binary_search(&occurrence).is_err() {
+        return Some(occurrence);
+      }
+    }
   }
 }
 
 pub struct RRuleSetIterator {
   value_type: ValueType,
-  iter: rrule::RRuleSetIter,
+  iter: Peekable<Occurrences>,
+  /// Time zone to convert the occurrences into, if not the one of DTSTART.
+  timezone: Option<Timezone>,
+}
+
+impl RRuleSetIterator {
+  fn in_timezone(self, timezone: Timezone) -> Self {
+    Self {
+      timezone: Some(timezone),
+      ..self
+    }
+  }
+
+  /// Returns the next occurrence if it precedes `datetime`.
+  fn next_before(
+    &mut self,
+    datetime: &chrono::DateTime<Timezone>,
+    inclusive: bool,
+  ) -> Option<DateTime> {
+    self
+      .iter
+      .next_if(|occurrence| {
+        if inclusive {
+          occurrence <= datetime
+        } else {
+          occurrence < datetime
+        }
+      })
+      .map(|date_time| to_occurrence(&date_time, &self.value_type, self.timezone.as_ref()))
+  }
 }
 
 impl Iterator for RRuleSetIterator {
@@ -265,18 +1015,106 @@ impl Iterator for RRuleSetIterator {
 
   #[inline]
   fn next(&mut self) -> Option<Self::Item> {
-    self.iter.next().map(|date_time| {
-      let date_time: DateTime = (&date_time).into();
+    self
+      .iter
+      .next()
+      .map(|date_time| to_occurrence(&date_time, &self.value_type, self.timezone.as_ref()))
+  }
+}
 
-      if self.value_type == ValueType::Date {
-        DateTime {
-          time: None,
-          ..date_time
-        }
-      } else {
-        date_time
+pub struct RRuleSetReverseIterator<'a> {
+  rrule_set: &'a RRuleSet,
+  value_type: ValueType,
+  lower_bound: chrono::DateTime<Timezone>,
+  /// End of the next window, `None` once the start of the set is reached.
+  upper_bound: Option<chrono::DateTime<Timezone>>,
+  inclusive: bool,
+  window: Duration,
+  /// Occurrences of the current window in ascending order.
+  occurrences: Vec<chrono::DateTime<Timezone>>,
+}
+
+impl RRuleSetReverseIterator<'_> {
+  /// Returns the occurrences of the window starting at `from` and ending at
+  /// `upper_bound`, in ascending order.
+  fn window_occurrences(
+    &self,
+    from: &chrono::DateTime<Timezone>,
+    upper_bound: &chrono::DateTime<Timezone>,
+    inclusive: bool,
+  ) -> Result<Vec<chrono::DateTime<Timezone>>, Error> {
+    Ok(
+      self
+        .rrule_set
+        .occurrences(self.rrule_set.to_rrule_set_from(from)?)?
+        .skip_while(|occurrence| occurrence < from)
+        .take_while(|occurrence| {
+          if inclusive {
+            occurrence <= upper_bound
+          } else {
+            occurrence < upper_bound
+          }
+        })
+        .collect(),
+    )
+  }
+}
+
+impl Iterator for RRuleSetReverseIterator<'_> {
+  type Item = Result<DateTime, Error>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    loop {
+      if let Some(date_time) = self.occurrences.pop() {
+        return Some(Ok(to_occurrence(&date_time, &self.value_type, None)));
       }
-    })
+
+      let upper_bound = self.upper_bound.take()?;
+      let inclusive = self.inclusive;
+      let from = upper_bound
+        .clone()
+        .checked_sub_signed(self.window)
+        .filter(|from| *from > self.lower_bound)
+        .unwrap_or_else(|| self.lower_bound.clone());
+      // Sets which can't seek, e.g. limited by COUNT, are iterated from DTSTART
+      // for every window, so they are rather collected at once
+      let from = match self.rrule_set.seek(&from) {
+        Ok(Some(_)) => from,
+        Ok(None) => self.lower_bound.clone(),
+        Err(err) => return Some(Err(err)),
+      };
+
+      self.occurrences = match self.window_occurrences(&from, &upper_bound, inclusive) {
+        Ok(occurrences) => occurrences,
+        Err(err) => return Some(Err(err)),
+      };
+
+      if from > self.lower_bound {
+        self.upper_bound = Some(from);
+        self.inclusive = false;
+      }
+
+      if self.occurrences.is_empty() {
+        self.window = self.window.checked_mul(2).unwrap_or(Duration::MAX);
+      }
+    }
+  }
+}
+
+/// Converts an occurrence into a datetime, in `timezone` if given. Dates are
+/// not converted, as they are bound to no instant.
+fn to_occurrence(
+  date_time: &chrono::DateTime<Timezone>,
+  value_type: &ValueType,
+  timezone: Option<&Timezone>,
+) -> DateTime {
+  match (value_type, timezone) {
+    (ValueType::Date, _) => DateTime {
+      time: None,
+      ..date_time.into()
+    },
+    (_, Some(timezone)) => (&date_time.with_timezone(timezone)).into(),
+    (_, None) => date_time.into(),
   }
 }
 
@@ -1,3 +1,7 @@
+use chrono::FixedOffset;
+
+use crate::{error::Error, rrule::timezone::Timezone};
+
 #[derive(Clone)]
 pub struct Time {
   pub hour: u32,
@@ -22,4 +26,16 @@ impl Time {
   pub fn offset(&self) -> Option<i32> {
     self.offset
   }
+
+  /// Returns the time zone the time is bound to by its offset in seconds,
+  /// either UTC or a fixed offset, or `None` for floating time.
+  pub fn timezone(&self) -> Result<Option<Timezone>, Error> {
+    match self.offset {
+      None => Ok(None),
+      Some(0) => Ok(Some(Timezone::UTC)),
+      Some(offset) => FixedOffset::east_opt(offset)
+        .map(|offset| Some(Timezone::Fixed(offset)))
+        .ok_or_else(|| Error::invalid_value("offset", &offset.to_string())),
+    }
+  }
 }
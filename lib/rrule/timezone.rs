@@ -0,0 +1,482 @@
+use super::{
+  datetime::DateTime,
+  disambiguation::Disambiguation,
+  dtstart::DtStart,
+  rrule::{RRule, ToRRule},
+};
+use crate::{
+  error::Error,
+  serialization::{component::Component, properties::Properties, property::Property},
+};
+use chrono::{
+  Datelike, Duration, FixedOffset, MappedLocalTime, NaiveDate, NaiveDateTime, NaiveTime, Offset,
+  TimeZone, Utc,
+};
+use std::{collections::HashMap, fmt, str::FromStr, sync::Arc};
+
+mod vtimezone;
+mod windows;
+
+/// Recurring observances of custom time zones are expanded up to this year,
+/// the offset of the last transition applies afterwards.
+const MAX_TRANSITION_YEAR: i32 = 2200;
+
+/// Time zone named by a TZID parameter: either an IANA time zone, a Windows
+/// time zone mapped to an IANA one or a custom one defined by a VTIMEZONE
+/// component. Datetimes given without TZID are placed into time zones of
+/// their own, depending on their offset.
+#[derive(Clone, Debug)]
+pub enum Timezone {
+  Tz(chrono_tz::Tz),
+  /// Wall-clock time not bound to any time zone. It's iterated the same way
+  /// as UTC, but its datetimes carry no offset.
+  Floating,
+  /// Fixed offset from UTC, e.g. of an ISO 8601 timestamp.
+  Fixed(FixedOffset),
+  /// Windows time zone, keeping its name to be written back verbatim.
+  Windows {
+    name: &'static str,
+    tz: chrono_tz::Tz,
+  },
+  Custom(Arc<CustomTimezone>),
+}
+
+impl Timezone {
+  pub const UTC: Timezone = Timezone::Tz(chrono_tz::Tz::UTC);
+
+  pub fn custom(&self) -> Option<&Arc<CustomTimezone>> {
+    match self {
+      Timezone::Custom(custom) => Some(custom),
+      _ => None,
+    }
+  }
+
+  /// Returns the VTIMEZONE component defining the time zone over the years
+  /// `from` to `until`, or from `from` on if `until` is `None`. Floating time
+  /// and fixed offsets are written without TZID, so they need none.
+  pub fn to_component(&self, from: i32, until: Option<i32>) -> Option<Component> {
+    match self {
+      Timezone::Tz(tz) | Timezone::Windows { tz, .. } => {
+        Some(vtimezone::to_component(tz, &self.to_string(), from, until))
+      }
+      Timezone::Custom(custom) => Some(custom.component().clone()),
+      Timezone::Floating | Timezone::Fixed(_) => None,
+    }
+  }
+
+  /// Converts `datetime` into the engine representation of the time zone.
+  ///
+  /// The engine iterates in UTC standing in for the wall-clock time of the
+  /// time zone, so that the wall-clock times of generated occurrences are
+  /// resolved by `Timezone::localize`, however the time zone is defined.
+  pub fn to_engine<Tz: TimeZone>(
+    &self,
+    datetime: &chrono::DateTime<Tz>,
+  ) -> chrono::DateTime<rrule::Tz> {
+    rrule::Tz::UTC.from_utc_datetime(&datetime.with_timezone(self).naive_local())
+  }
+
+  /// Converts a datetime produced by the engine back into the time zone,
+  /// resolving wall-clock times in DST gaps and overlaps by `disambiguation`.
+  pub fn localize(
+    &self,
+    datetime: &chrono::DateTime<rrule::Tz>,
+    disambiguation: Disambiguation,
+  ) -> Option<chrono::DateTime<Timezone>> {
+    disambiguation.resolve_occurrence(self, &datetime.naive_utc())
+  }
+}
+
+impl PartialEq for Timezone {
+  fn eq(&self, other: &Self) -> bool {
+    match (self, other) {
+      (Timezone::Tz(tz), Timezone::Tz(other)) => tz == other,
+      (Timezone::Floating, Timezone::Floating) => true,
+      (Timezone::Fixed(offset), Timezone::Fixed(other)) => offset == other,
+      (
+        Timezone::Windows { name, tz },
+        Timezone::Windows {
+          name: other_name,
+          tz: other,
+        },
+      ) => name == other_name && tz == other,
+      (Timezone::Custom(custom), Timezone::Custom(other)) => {
+        Arc::ptr_eq(custom, other)
+          || (custom.tzid == other.tzid
+            && custom.initial_offset == other.initial_offset
+            && custom.transitions == other.transitions)
+      }
+      _ => false,
+    }
+  }
+}
+
+impl From<chrono_tz::Tz> for Timezone {
+  fn from(tz: chrono_tz::Tz) -> Self {
+    Timezone::Tz(tz)
+  }
+}
+
+impl FromStr for Timezone {
+  type Err = Error;
+
+  /// Parses an IANA or a Windows time zone name. Custom time zones are
+  /// resolved through `Timezones::resolve` instead.
+  fn from_str(str: &str) -> Result<Self, Self::Err> {
+    if let Ok(tz) = str.parse::<chrono_tz::Tz>() {
+      return Ok(Timezone::Tz(tz));
+    }
+
+    windows::find(str)
+      .map(|(name, tz)| Timezone::Windows { name, tz })
+      .ok_or_else(|| Error::InvalidTimezone(str.to_string()))
+  }
+}
+
+impl fmt::Display for Timezone {
+  /// Writes the TZID of the time zone.
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      Timezone::Tz(tz) => write!(f, "{}", tz.name()),
+      Timezone::Floating => write!(f, "floating"),
+      Timezone::Fixed(offset) => write!(f, "{}", offset),
+      Timezone::Windows { name, .. } => write!(f, "{}", name),
+      Timezone::Custom(custom) => write!(f, "{}", custom.tzid),
+    }
+  }
+}
+
+/// Offset of a `Timezone` at some instant.
+#[derive(Clone, Debug)]
+pub struct TimezoneOffset {
+  timezone: Timezone,
+  offset: FixedOffset,
+}
+
+impl Offset for TimezoneOffset {
+  fn fix(&self) -> FixedOffset {
+    self.offset
+  }
+}
+
+impl fmt::Display for TimezoneOffset {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}", self.offset)
+  }
+}
+
+impl TimeZone for Timezone {
+  type Offset = TimezoneOffset;
+
+  fn from_offset(offset: &Self::Offset) -> Self {
+    offset.timezone.clone()
+  }
+
+  fn offset_from_local_date(&self, local: &NaiveDate) -> MappedLocalTime<Self::Offset> {
+    self.offset_from_local_datetime(&local.and_time(NaiveTime::MIN))
+  }
+
+  fn offset_from_local_datetime(&self, local: &NaiveDateTime) -> MappedLocalTime<Self::Offset> {
+    let offset = match self {
+      Timezone::Tz(tz) | Timezone::Windows { tz, .. } => tz
+        .offset_from_local_datetime(local)
+        .map(|offset| offset.fix()),
+      Timezone::Floating => MappedLocalTime::Single(Utc.fix()),
+      Timezone::Fixed(offset) => MappedLocalTime::Single(*offset),
+      Timezone::Custom(custom) => custom.offset_from_local_datetime(local),
+    };
+
+    offset.map(|offset| TimezoneOffset {
+      timezone: self.clone(),
+      offset,
+    })
+  }
+
+  fn offset_from_utc_date(&self, utc: &NaiveDate) -> Self::Offset {
+    self.offset_from_utc_datetime(&utc.and_time(NaiveTime::MIN))
+  }
+
+  fn offset_from_utc_datetime(&self, utc: &NaiveDateTime) -> Self::Offset {
+    let offset = match self {
+      Timezone::Tz(tz) | Timezone::Windows { tz, .. } => tz.offset_from_utc_datetime(utc).fix(),
+      Timezone::Floating => Utc.fix(),
+      Timezone::Fixed(offset) => *offset,
+      Timezone::Custom(custom) => custom.offset_at(utc),
+    };
+
+    TimezoneOffset {
+      timezone: self.clone(),
+      offset,
+    }
+  }
+}
+
+/// Time zone defined by the STANDARD and DAYLIGHT observances of a VTIMEZONE
+/// component.
+#[derive(Clone)]
+pub struct CustomTimezone {
+  tzid: String,
+  /// Offset in effect before the first transition.
+  initial_offset: FixedOffset,
+  /// UTC onsets of the observances in ascending order, along with the offset
+  /// they switch to.
+  transitions: Vec<(NaiveDateTime, FixedOffset)>,
+  /// The VTIMEZONE component, kept to be written along with the TZID.
+  component: Component,
+}
+
+impl CustomTimezone {
+  pub fn tzid(&self) -> &str {
+    &self.tzid
+  }
+
+  pub fn component(&self) -> &Component {
+    &self.component
+  }
+
+  pub fn from_component(component: &Component) -> Result<Self, Error> {
+    let tzid = component
+      .property("TZID")
+      .map(|property| property.value().to_string())
+      .ok_or_else(|| component.locate(Error::MissingProperty("TZID".to_string())))?;
+    let mut onsets: Vec<(NaiveDateTime, FixedOffset, FixedOffset)> = Vec::new();
+
+    for observance in component.components() {
+      if matches!(observance.name(), "STANDARD" | "DAYLIGHT") {
+        onsets.extend(observance_onsets(observance).map_err(|err| observance.locate(err))?);
+      }
+    }
+
+    onsets.sort_by_key(|(onset, _, _)| *onset);
+    onsets.dedup_by_key(|(onset, _, _)| *onset);
+
+    let initial_offset = match onsets.first() {
+      Some((_, offset_from, _)) => *offset_from,
+      None => return Err(component.locate(Error::MissingProperty("STANDARD".to_string()))),
+    };
+
+    Ok(Self {
+      tzid,
+      initial_offset,
+      transitions: onsets
+        .into_iter()
+        .map(|(onset, _, offset_to)| (onset, offset_to))
+        .collect(),
+      component: component.clone(),
+    })
+  }
+
+  /// Returns the offset in effect at the UTC datetime `utc`.
+  fn offset_at(&self, utc: &NaiveDateTime) -> FixedOffset {
+    match self.transitions.partition_point(|(onset, _)| onset <= utc) {
+      0 => self.initial_offset,
+      index => self.transitions[index - 1].1,
+    }
+  }
+
+  /// Returns the offsets the wall-clock time `local` may be observed at. Zero
+  /// offsets mean that it falls into a gap, two offsets into an overlap.
+  fn offset_from_local_datetime(&self, local: &NaiveDateTime) -> MappedLocalTime<FixedOffset> {
+    // Transitions are assumed to be at least a day apart, so one of the
+    // offsets around `local` applies unless `local` is skipped
+    let mut offsets: Vec<FixedOffset> = [
+      self.offset_at(&(*local - Duration::days(1))),
+      self.offset_at(&(*local + Duration::days(1))),
+    ]
+    .into_iter()
+    .filter(|offset| self.offset_at(&(*local - *offset)) == *offset)
+    .collect();
+
+    offsets.sort_by_key(|offset| std::cmp::Reverse(offset.local_minus_utc()));
+    offsets.dedup();
+
+    match offsets[..] {
+      [] => MappedLocalTime::None,
+      [offset] => MappedLocalTime::Single(offset),
+      [earliest, latest, ..] => MappedLocalTime::Ambiguous(earliest, latest),
+    }
+  }
+}
+
+impl fmt::Debug for CustomTimezone {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.debug_struct("CustomTimezone")
+      .field("tzid", &self.tzid)
+      .field("initial_offset", &self.initial_offset)
+      .field("transitions", &self.transitions.len())
+      .finish()
+  }
+}
+
+/// Custom time zones by TZID, e.g. as defined by the VTIMEZONE components of
+/// an iCalendar document.
+#[derive(Clone, Default)]
+pub struct Timezones {
+  custom: HashMap<String, Timezone>,
+}
+
+impl Timezones {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  pub fn insert(&mut self, timezone: CustomTimezone) {
+    self
+      .custom
+      .insert(timezone.tzid.clone(), Timezone::Custom(Arc::new(timezone)));
+  }
+
+  /// Parses the VTIMEZONE components of each of the iCalendar `definitions`.
+  pub fn from_definitions(definitions: &[String]) -> Result<Self, Error> {
+    let mut timezones = Timezones::new();
+
+    for definition in definitions {
+      let properties: Properties = definition.parse()?;
+
+      timezones.extend_from_components(&Component::from_properties(properties)?)?;
+    }
+
+    Ok(timezones)
+  }
+
+  /// Adds the time zones defined by the VTIMEZONE `components`, either at the
+  /// top level or nested in VCALENDAR.
+  pub fn extend_from_components(&mut self, components: &[Component]) -> Result<(), Error> {
+    for component in components {
+      match component.name() {
+        "VTIMEZONE" => self.insert(CustomTimezone::from_component(component)?),
+        "VCALENDAR" => self.extend_from_components(component.components())?,
+        _ => {}
+      }
+    }
+
+    Ok(())
+  }
+
+  /// Resolves `tzid` to an IANA time zone, or to a custom time zone if it
+  /// names none. Windows time zones come last, so that the VTIMEZONE
+  /// components exported along with them are kept.
+  pub fn resolve(&self, tzid: &str) -> Result<Timezone, Error> {
+    match (tzid.parse::<chrono_tz::Tz>(), self.custom.get(tzid)) {
+      (Ok(tz), _) => Ok(Timezone::Tz(tz)),
+      (Err(_), Some(custom)) => Ok(custom.clone()),
+      (Err(_), None) => tzid.parse(),
+    }
+  }
+}
+
+/// Returns the UTC onsets of a STANDARD or DAYLIGHT observance along with the
+/// offsets it switches from and to.
+fn observance_onsets(
+  observance: &Component,
+) -> Result<Vec<(NaiveDateTime, FixedOffset, FixedOffset)>, Error> {
+  let required = |name: &str| {
+    observance
+      .property(name)
+      .ok_or_else(|| Error::MissingProperty(name.to_string()))
+  };
+  let offset_from = parse_offset(required("TZOFFSETFROM")?)?;
+  let offset_to = parse_offset(required("TZOFFSETTO")?)?;
+  let dtstart = parse_local(required("DTSTART")?)?;
+  let mut onsets = vec![dtstart];
+
+  for property in observance.properties() {
+    match property.name() {
+      "RRULE" => onsets.extend(expand(property, dtstart, offset_from)?),
+      "RDATE" => {
+        for value in property.value().to_string().split(',') {
+          onsets.push(parse_local_value(property, value)?);
+        }
+      }
+      _ => {}
+    }
+  }
+
+  Ok(
+    onsets
+      .into_iter()
+      .map(|onset| (onset - offset_from, offset_from, offset_to))
+      .collect(),
+  )
+}
+
+/// Expands the RRULE of an observance started at the wall-clock time
+/// `dtstart`. UNTIL is given in UTC, so it's moved to wall-clock time as well.
+fn expand(
+  property: &Property,
+  dtstart: NaiveDateTime,
+  offset_from: FixedOffset,
+) -> Result<Vec<NaiveDateTime>, Error> {
+  let mut rrule = RRule::from_property(property).map_err(|err| property.locate(err))?;
+
+  if let Some(until) = rrule.until() {
+    let until = until
+      .to_datetime(&Timezone::UTC, Disambiguation::Reject)?
+      .naive_utc()
+      + offset_from;
+
+    rrule = rrule.set_until(Some(to_utc(until)));
+  }
+
+  let dtstart = DtStart::new(to_utc(dtstart), None, None)?;
+  let start = dtstart
+    .to_datetime(Disambiguation::Reject)?
+    .with_timezone(&rrule::Tz::UTC);
+  let rrule = rrule.to_rrule(&dtstart, Disambiguation::Reject)?;
+
+  Ok(
+    rrule::RRuleSet::new(start)
+      .rrule(rrule)
+      .into_iter()
+      .map(|onset| onset.naive_utc())
+      .take_while(|onset| onset.year() <= MAX_TRANSITION_YEAR)
+      .collect(),
+  )
+}
+
+fn parse_offset(property: &Property) -> Result<FixedOffset, Error> {
+  let value = property.value().to_string();
+  let invalid = || property.locate(Error::invalid_property_value(property.name(), &value));
+  let (sign, digits) = match value.split_at_checked(1) {
+    Some(("+", digits)) => (1, digits),
+    Some(("-", digits)) => (-1, digits),
+    _ => return Err(invalid()),
+  };
+
+  if !matches!(digits.len(), 4 | 6) || !digits.bytes().all(|byte| byte.is_ascii_digit()) {
+    return Err(invalid());
+  }
+
+  let part = |index: usize| {
+    digits
+      .get(index..index + 2)
+      .map_or(0, |part| part.parse().unwrap_or(0))
+  };
+  let seconds = part(0) * 3600 + part(2) * 60 + part(4);
+
+  FixedOffset::east_opt(sign * seconds).ok_or_else(invalid)
+}
+
+fn parse_local(property: &Property) -> Result<NaiveDateTime, Error> {
+  parse_local_value(property, &property.value().to_string())
+}
+
+/// Parses the wall-clock DATE-TIME `value` of `property`.
+fn parse_local_value(property: &Property, value: &str) -> Result<NaiveDateTime, Error> {
+  value
+    .parse::<DateTime>()
+    .ok()
+    .filter(|datetime| datetime.time().is_some())
+    .and_then(|datetime| {
+      datetime
+        .to_datetime(&Timezone::UTC, Disambiguation::Reject)
+        .ok()
+    })
+    .map(|datetime| datetime.naive_utc())
+    .ok_or_else(|| property.locate(Error::invalid_property_value(property.name(), value)))
+}
+
+/// Returns the UTC datetime standing in for the wall-clock time `local`.
+fn to_utc(local: NaiveDateTime) -> DateTime {
+  (&Timezone::UTC.from_utc_datetime(&local)).into()
+}
@@ -0,0 +1,276 @@
+use crate::{
+  rrule::{
+    datetime::{format_offset, DateTime},
+    frequency::Frequency,
+    n_weekday::NWeekday,
+    rrule::RRule,
+    time::Time,
+  },
+  serialization::{
+    component::Component,
+    parameters::Parameters,
+    property::{Property, Value},
+  },
+};
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Offset, TimeZone, Timelike};
+use chrono_tz::{OffsetComponents, OffsetName, Tz};
+
+/// chrono-tz lists the transitions of time zones up to this year, their
+/// offsets stay as is afterwards. Observances recurring until then are
+/// written to recur forever, as the rules they follow are still in force.
+const LAST_LISTED_YEAR: i32 = 2099;
+
+/// Offset observed in a time zone, along with its abbreviation and whether
+/// it's daylight saving time.
+#[derive(Clone, PartialEq)]
+struct Observance {
+  offset: i32,
+  name: Option<String>,
+  daylight: bool,
+}
+
+impl Observance {
+  fn at(tz: &Tz, utc: &NaiveDateTime) -> Self {
+    let offset = tz.offset_from_utc_datetime(utc);
+
+    Self {
+      offset: offset.fix().local_minus_utc(),
+      name: offset.abbreviation().map(str::to_string),
+      daylight: !offset.dst_offset().is_zero(),
+    }
+  }
+}
+
+/// Yearly rule a transition follows: its month, its weekday within the month
+/// as in BYDAY, e.g. -1 for the last one, and its wall-clock time.
+#[derive(Clone, PartialEq)]
+struct Rule {
+  month: u32,
+  n: i16,
+  weekday: chrono::Weekday,
+  time: NaiveTime,
+}
+
+/// Change of the observance of a time zone at a UTC instant.
+struct Transition {
+  utc: NaiveDateTime,
+  from: Observance,
+  to: Observance,
+}
+
+impl Transition {
+  /// Returns the wall-clock time the transition happens at, observed before
+  /// it.
+  fn local(&self) -> NaiveDateTime {
+    self.utc + Duration::seconds(self.from.offset.into())
+  }
+
+  fn rules(&self) -> Vec<Rule> {
+    let local = self.local();
+    let days_in_month = NaiveDate::from_ymd_opt(local.year(), local.month(), 1)
+      .and_then(|first| first.checked_add_months(chrono::Months::new(1)))
+      .and_then(|next| next.pred_opt())
+      .map_or(31, |last| last.day());
+    let rule = |n: i16| Rule {
+      month: local.month(),
+      n,
+      weekday: local.weekday(),
+      time: local.time(),
+    };
+    let mut rules = Vec::new();
+
+    if local.day() + 7 > days_in_month {
+      rules.push(rule(-1));
+    }
+
+    if local.day() <= 28 {
+      rules.push(rule(((local.day() - 1) / 7 + 1) as i16));
+    }
+
+    rules
+  }
+}
+
+/// Transitions of the same kind in consecutive years following a common
+/// yearly rule.
+struct Run<'a> {
+  first: &'a Transition,
+  last: &'a Transition,
+  rules: Vec<Rule>,
+}
+
+/// Generates the VTIMEZONE component defining `tz` under the name `tzid`
+/// over the years `from` to `until`, or from `from` on if `until` is `None`.
+///
+/// The observance in effect at the start comes first, followed by the
+/// transitions, written as yearly recurring observances where they follow a
+/// rule over several years.
+pub fn to_component(tz: &Tz, tzid: &str, from: i32, until: Option<i32>) -> Component {
+  let last_year = until.unwrap_or(from.max(LAST_LISTED_YEAR));
+  // Starts a day early, so that transitions at the very start are listed
+  let start = year_start(from) - Duration::days(1);
+  let initial = Observance::at(tz, &start);
+  let transitions = transitions(
+    tz,
+    start - Duration::seconds(initial.offset.into()),
+    year_start(last_year.saturating_add(1)),
+  );
+  let mut runs: Vec<Run> = Vec::new();
+
+  for transition in transitions.iter() {
+    let rules = transition.rules();
+    let run = runs
+      .iter_mut()
+      .rev()
+      .find(|run| run.first.from == transition.from && run.first.to == transition.to)
+      .filter(|run| run.last.local().year() + 1 == transition.local().year())
+      .filter(|run| run.rules.iter().any(|rule| rules.contains(rule)));
+
+    match run {
+      Some(run) => {
+        run.rules.retain(|rule| rules.contains(rule));
+        run.last = transition;
+      }
+      None => runs.push(Run {
+        first: transition,
+        last: transition,
+        rules,
+      }),
+    }
+  }
+
+  let mut component = Component::new("VTIMEZONE".to_string());
+
+  component.push_property(single("TZID", tzid.to_string()));
+  component.push_component(observance(&initial, &initial, start, None));
+
+  for run in runs {
+    let rrule = match run.rules.first() {
+      Some(rule) if !std::ptr::eq(run.first, run.last) => {
+        let open = until.is_none() && run.last.local().year() >= LAST_LISTED_YEAR;
+
+        to_rrule(rule, (!open).then(|| to_utc(run.last.utc)))
+      }
+      _ => None,
+    };
+
+    component.push_component(observance(
+      &run.first.from,
+      &run.first.to,
+      run.first.local(),
+      rrule,
+    ));
+  }
+
+  component
+}
+
+/// Finds the transitions of `tz` between the UTC datetimes `start` and `end`,
+/// assuming they are at least a day apart.
+fn transitions(tz: &Tz, start: NaiveDateTime, end: NaiveDateTime) -> Vec<Transition> {
+  let mut transitions = Vec::new();
+  let mut datetime = start;
+  let mut observance = Observance::at(tz, &datetime);
+
+  while datetime < end {
+    let next = datetime + Duration::days(1);
+    let next_observance = Observance::at(tz, &next);
+
+    if next_observance != observance {
+      // The transition is within (low, high]
+      let (mut low, mut high) = (datetime, next);
+
+      while high - low > Duration::seconds(1) {
+        let middle = low + (high - low) / 2;
+
+        match Observance::at(tz, &middle) == observance {
+          true => low = middle,
+          false => high = middle,
+        }
+      }
+
+      transitions.push(Transition {
+        utc: high,
+        from: observance,
+        to: Observance::at(tz, &high),
+      });
+    }
+
+    datetime = next;
+    observance = next_observance;
+  }
+
+  transitions
+}
+
+/// Returns a STANDARD or DAYLIGHT component switching from the observance
+/// `from` to `to` at the wall-clock time `onset`.
+fn observance(
+  from: &Observance,
+  to: &Observance,
+  onset: NaiveDateTime,
+  rrule: Option<RRule>,
+) -> Component {
+  let name = if to.daylight { "DAYLIGHT" } else { "STANDARD" };
+  let mut component = Component::new(name.to_string());
+
+  component.push_property(single("DTSTART", to_local(onset).to_string()));
+  component.push_property(single("TZOFFSETFROM", format_offset(from.offset)));
+  component.push_property(single("TZOFFSETTO", format_offset(to.offset)));
+
+  if let Some(name) = &to.name {
+    component.push_property(single("TZNAME", name.clone()));
+  }
+
+  if let Some(rrule) = rrule {
+    component.push_property(rrule.to_property());
+  }
+
+  component
+}
+
+fn to_rrule(rule: &Rule, until: Option<DateTime>) -> Option<RRule> {
+  let month = rule.month.to_string().parse().ok()?;
+
+  Some(
+    RRule::new(Frequency::Yearly)
+      .set_by_month(vec![month])
+      .set_by_weekday(vec![NWeekday {
+        n: Some(rule.n),
+        weekday: rule.weekday.into(),
+      }])
+      .set_until(until),
+  )
+}
+
+fn year_start(year: i32) -> NaiveDateTime {
+  NaiveDate::from_ymd_opt(year, 1, 1)
+    .unwrap_or(NaiveDate::MAX)
+    .and_time(NaiveTime::MIN)
+}
+
+fn to_local(datetime: NaiveDateTime) -> DateTime {
+  to_datetime(datetime, None)
+}
+
+fn to_utc(datetime: NaiveDateTime) -> DateTime {
+  to_datetime(datetime, Some(0))
+}
+
+fn to_datetime(datetime: NaiveDateTime, offset: Option<i32>) -> DateTime {
+  DateTime {
+    year: datetime.year() as u32,
+    month: datetime.month(),
+    day: datetime.day(),
+    time: Some(Time {
+      hour: datetime.hour(),
+      minute: datetime.minute(),
+      second: datetime.second(),
+      offset,
+    }),
+  }
+}
+
+fn single(name: &str, value: String) -> Property {
+  Property::new(name.to_string(), Parameters::new(), Value::Single(value))
+}
@@ -0,0 +1,191 @@
+use chrono::{Datelike, NaiveDate};
+use itertools::Itertools;
+
+use super::{
+  diagnostic::{Diagnostic, DiagnosticCode},
+  frequency::Frequency,
+  rrule::RRule,
+};
+
+/// First year of the range dates are checked against. The Gregorian calendar
+/// repeats itself, weekdays included, every 400 years.
+const CYCLE_START: i32 = 2000;
+const CYCLE_YEARS: i32 = 400;
+
+/// Detects rules that can never produce an occurrence.
+///
+/// The analysis is conservative: only the parts a rule spells out are taken
+/// into account, BYWEEKNO and BYSETPOS are ignored, and so are the defaults
+/// later derived from DTSTART. A rule reported as impossible is guaranteed to
+/// be empty, but not every empty rule is reported.
+pub fn validate(rrule: &RRule) -> Vec<Diagnostic> {
+  let mut diagnostics = Vec::new();
+
+  if constrains_date(rrule) && !has_matching_date(rrule) {
+    diagnostics.push(Diagnostic::error(
+      DiagnosticCode::NoMatchingDate,
+      format!("No date satisfies {}", describe_date_parts(rrule)),
+    ));
+  }
+
+  diagnostics
+}
+
+/// Returns whether the rule restricts dates in a way that may exclude every
+/// date. A list of plain weekdays alone always matches some date.
+fn constrains_date(rrule: &RRule) -> bool {
+  !rrule.by_month().is_empty()
+    || !rrule.by_monthday().is_empty()
+    || !rrule.by_yearday().is_empty()
+    || has_nth_weekday(rrule)
+}
+
+/// Returns whether numeric BYDAY values are taken into account, which is only
+/// the case for YEARLY and MONTHLY rules.
+fn has_nth_weekday(rrule: &RRule) -> bool {
+  matches!(rrule.frequency(), Frequency::Yearly | Frequency::Monthly)
+    && rrule.by_weekday().iter().any(|nday| nday.n.is_some())
+}
+
+fn has_matching_date(rrule: &RRule) -> bool {
+  let months = rrule
+    .by_month()
+    .iter()
+    .map(|month| chrono::Month::from(month).number_from_month())
+    .collect::<Vec<_>>();
+
+  (CYCLE_START..CYCLE_START + CYCLE_YEARS).any(|year| {
+    (1..=12)
+      .filter(|month| months.is_empty() || months.contains(month))
+      .any(|month| {
+        let days_in_month = days_in_month(year, month);
+
+        (1..=days_in_month).any(|day| match NaiveDate::from_ymd_opt(year, month, day) {
+          Some(date) => matches_date(rrule, &date, days_in_month),
+          None => false,
+        })
+      })
+  })
+}
+
+fn matches_date(rrule: &RRule, date: &NaiveDate, days_in_month: u32) -> bool {
+  matches_monthday(rrule, date, days_in_month)
+    && matches_yearday(rrule, date)
+    && matches_weekday(rrule, date)
+    && matches_nth_weekday(rrule, date, days_in_month)
+}
+
+fn matches_monthday(rrule: &RRule, date: &NaiveDate, days_in_month: u32) -> bool {
+  let day = date.day() as i64;
+  let days_in_month = days_in_month as i64;
+
+  rrule.by_monthday().is_empty()
+    || rrule.by_monthday().iter().any(|monthday| {
+      let monthday = *monthday as i64;
+
+      monthday == day || monthday == day - days_in_month - 1
+    })
+}
+
+fn matches_yearday(rrule: &RRule, date: &NaiveDate) -> bool {
+  let ordinal = date.ordinal() as i64;
+  let days_in_year = days_in_year(date.year()) as i64;
+
+  rrule.by_yearday().is_empty()
+    || rrule.by_yearday().iter().any(|yearday| {
+      let yearday = *yearday as i64;
+
+      yearday == ordinal || yearday == ordinal - days_in_year - 1
+    })
+}
+
+/// Plain BYDAY values and numeric ones are applied as separate filters, the
+/// numeric prefix being ignored by frequencies other than YEARLY and MONTHLY.
+fn matches_weekday(rrule: &RRule, date: &NaiveDate) -> bool {
+  let mut weekdays = rrule
+    .by_weekday()
+    .iter()
+    .filter(|nday| nday.n.is_none())
+    .map(|nday| rrule::Weekday::from(&nday.weekday))
+    .peekable();
+
+  weekdays.peek().is_none() || weekdays.any(|weekday| weekday == date.weekday())
+}
+
+/// Numeric BYDAY values select the nth weekday of the month for MONTHLY
+/// rules, and of the year, or of each BYMONTH month, for YEARLY rules.
+fn matches_nth_weekday(rrule: &RRule, date: &NaiveDate, days_in_month: u32) -> bool {
+  if !has_nth_weekday(rrule) {
+    return true;
+  }
+
+  let (day, days) = match rrule.frequency() {
+    Frequency::Yearly if rrule.by_month().is_empty() => (date.ordinal(), days_in_year(date.year())),
+    _ => (date.day(), days_in_month),
+  };
+  let position = ((day - 1) / 7 + 1) as i16;
+  let negative_position = -(((days - day) / 7 + 1) as i16);
+
+  rrule.by_weekday().iter().any(|nday| match nday.n {
+    Some(n) => {
+      rrule::Weekday::from(&nday.weekday) == date.weekday()
+        && (n == position || n == negative_position)
+    }
+    None => false,
+  })
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+  let (next_year, next_month) = if month == 12 {
+    (year + 1, 1)
+  } else {
+    (year, month + 1)
+  };
+
+  NaiveDate::from_ymd_opt(next_year, next_month, 1)
+    .and_then(|date| date.pred_opt())
+    .map(|date| date.day())
+    .unwrap_or(31)
+}
+
+fn days_in_year(year: i32) -> u32 {
+  if NaiveDate::from_ymd_opt(year, 2, 29).is_some() {
+    366
+  } else {
+    365
+  }
+}
+
+/// Renders the parts involved in the date analysis, e.g.
+/// `BYMONTH=2;BYMONTHDAY=30`.
+fn describe_date_parts(rrule: &RRule) -> String {
+  let mut parts = Vec::new();
+
+  if !rrule.by_month().is_empty() {
+    parts.push(format!("BYMONTH={}", rrule.by_month().iter().join(",")));
+  }
+
+  if !rrule.by_monthday().is_empty() {
+    parts.push(format!(
+      "BYMONTHDAY={}",
+      rrule.by_monthday().iter().join(",")
+    ));
+  }
+
+  if !rrule.by_yearday().is_empty() {
+    parts.push(format!("BYYEARDAY={}", rrule.by_yearday().iter().join(",")));
+  }
+
+  if !rrule.by_weekday().is_empty() {
+    parts.push(format!(
+      "BYDAY={}",
+      rrule
+        .by_weekday()
+        .iter()
+        .map(Into::<String>::into)
+        .join(",")
+    ));
+  }
+
+  parts.join(";")
+}
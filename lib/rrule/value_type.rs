@@ -1,5 +1,7 @@
 use std::{fmt, str::FromStr};
 
+use crate::error::Error;
+
 #[derive(Clone, Debug, PartialEq)]
 pub enum ValueType {
   Date,
@@ -7,13 +9,13 @@ pub enum ValueType {
 }
 
 impl FromStr for ValueType {
-  type Err = String;
+  type Err = Error;
 
   fn from_str(s: &str) -> Result<Self, Self::Err> {
     match s.to_uppercase().as_str() {
       "DATE" => Ok(ValueType::Date),
       "DATE-TIME" => Ok(ValueType::DateTime),
-      _ => Err(format!("Invalid value type: {}", s)),
+      _ => Err(Error::invalid_value("VALUE", s)),
     }
   }
 }
@@ -1,5 +1,7 @@
 use std::str::FromStr;
 
+use crate::error::Error;
+
 #[derive(PartialEq, Clone, Default)]
 pub enum Weekday {
   #[default]
@@ -67,7 +69,7 @@ impl From<&Weekday> for String {
 }
 
 impl FromStr for Weekday {
-  type Err = String;
+  type Err = Error;
 
   fn from_str(str: &str) -> Result<Self, Self::Err> {
     match &*str.to_uppercase() {
@@ -78,7 +80,7 @@ impl FromStr for Weekday {
       "FR" => Ok(Weekday::Friday),
       "SA" => Ok(Weekday::Saturday),
       "SU" => Ok(Weekday::Sunday),
-      _ => Err(format!("Invalid weekday: {}", str)),
+      _ => Err(Error::invalid_value("weekday", str)),
     }
   }
 }
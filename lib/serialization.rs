@@ -1,3 +1,5 @@
+pub mod component;
 pub mod parameters;
 pub mod properties;
 pub mod property;
+pub mod text;
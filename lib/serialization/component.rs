@@ -0,0 +1,143 @@
+use super::{
+  parameters::Parameters,
+  properties::Properties,
+  property::{self, Property, Value},
+};
+use crate::error::{Error, Location};
+
+/// Calendar component delimited by `BEGIN` and `END` properties, e.g. VEVENT.
+#[derive(Clone)]
+pub struct Component {
+  name: String,
+  properties: Vec<Property>,
+  components: Vec<Component>,
+  /// Location of the `BEGIN` property in the parsed input.
+  location: Option<Location>,
+}
+
+impl Component {
+  pub fn new(name: String) -> Self {
+    Self {
+      name,
+      properties: Vec::new(),
+      components: Vec::new(),
+      location: None,
+    }
+  }
+
+  pub fn push_property(&mut self, property: Property) {
+    self.properties.push(property);
+  }
+
+  pub fn push_component(&mut self, component: Component) {
+    self.components.push(component);
+  }
+
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  pub fn properties(&self) -> &Vec<Property> {
+    &self.properties
+  }
+
+  pub fn components(&self) -> &Vec<Component> {
+    &self.components
+  }
+
+  /// Returns the first property named `name`.
+  pub fn property(&self, name: &str) -> Option<&Property> {
+    self
+      .properties
+      .iter()
+      .find(|property| property.name() == name)
+  }
+
+  /// Attaches the location of the component to `err`, unless it has one.
+  pub fn locate(&self, err: Error) -> Error {
+    match &self.location {
+      Some(location) => err.at(location.clone()),
+      None => err,
+    }
+  }
+
+  /// Flattens the component into its properties, nested components included,
+  /// delimited by `BEGIN` and `END` properties.
+  pub fn to_properties(&self) -> Properties {
+    let mut properties = Properties::new();
+
+    self.push_to(&mut properties);
+
+    properties
+  }
+
+  fn push_to(&self, properties: &mut Properties) {
+    let delimiter = |name: &str| {
+      Property::new(
+        name.to_string(),
+        Parameters::new(),
+        Value::Single(self.name.clone()),
+      )
+    };
+
+    properties.push(delimiter("BEGIN"));
+
+    for property in self.properties.iter() {
+      properties.push(property.clone());
+    }
+
+    for component in self.components.iter() {
+      component.push_to(properties);
+    }
+
+    properties.push(delimiter("END"));
+  }
+
+  /// Groups `properties` into the components delimited by their `BEGIN` and
+  /// `END` properties. Properties outside of any component are ignored.
+  pub fn from_properties(properties: Properties) -> Result<Vec<Component>, Error> {
+    let mut components = Vec::new();
+    let mut stack: Vec<Component> = Vec::new();
+
+    for property in properties {
+      match property.name() {
+        "BEGIN" => {
+          let name = property.value().to_string().to_uppercase();
+
+          if !property::is_name(&name) {
+            return Err(property.locate(Error::InvalidProperty(format!("BEGIN:{}", name))));
+          }
+
+          stack.push(Component {
+            name,
+            properties: Vec::new(),
+            components: Vec::new(),
+            location: property.location().cloned(),
+          });
+        }
+        "END" => {
+          let name = property.value().to_string().to_uppercase();
+          let component = match stack.pop() {
+            Some(component) if component.name == name => component,
+            _ => return Err(property.locate(Error::UnexpectedEnd(name))),
+          };
+
+          match stack.last_mut() {
+            Some(parent) => parent.components.push(component),
+            None => components.push(component),
+          }
+        }
+        _ => {
+          if let Some(component) = stack.last_mut() {
+            component.properties.push(property);
+          }
+        }
+      }
+    }
+
+    match stack.pop() {
+      Some(component) => Err(component.locate(Error::UnclosedComponent(component.name.clone()))),
+      None => Ok(components),
+    }
+  }
+}
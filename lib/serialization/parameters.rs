@@ -2,8 +2,12 @@ use std::{ops::Index, str::FromStr};
 
 use indexmap::IndexMap;
 
+use crate::error::Error;
+
+/// Named parameters, each with one or more values.
+#[derive(Clone)]
 pub struct Parameters {
-  params: IndexMap<String, String>,
+  params: IndexMap<String, Vec<String>>,
 }
 
 impl Default for Parameters {
@@ -20,26 +24,39 @@ impl Parameters {
   }
 
   pub fn insert(&mut self, key: String, value: String) {
-    self.params.insert(key.to_uppercase(), value);
+    self.params.insert(key.to_uppercase(), vec![value]);
+  }
+
+  /// Inserts a parameter holding a list of values, e.g. `MEMBER="a","b"`.
+  pub fn insert_values(&mut self, key: String, values: Vec<String>) {
+    self.params.insert(key.to_uppercase(), values);
   }
 
+  /// Returns the first value of `key`.
   pub fn get(&self, key: &str) -> Option<&String> {
+    self
+      .params
+      .get(&key.to_uppercase())
+      .and_then(|values| values.first())
+  }
+
+  pub fn get_values(&self, key: &str) -> Option<&Vec<String>> {
     self.params.get(&key.to_uppercase())
   }
 
-  pub fn get_as<T: FromStr>(&self, key: &str) -> Result<Option<T>, String> {
+  pub fn get_as<T: FromStr>(&self, key: &str) -> Result<Option<T>, Error> {
     match self.get(key) {
       Some(value) => Ok(Some(value_as::<T>(key, value)?)),
       None => Ok(None),
     }
   }
 
-  pub fn get_as_vec<T: FromStr>(&self, key: &str) -> Result<Option<Vec<T>>, String> {
-    match self.get(key) {
-      Some(value) => {
+  pub fn get_as_vec<T: FromStr>(&self, key: &str) -> Result<Option<Vec<T>>, Error> {
+    match self.get_values(key) {
+      Some(values) => {
         let mut data = Vec::<T>::new();
 
-        for item in value.split(',') {
+        for item in values.iter().flat_map(|value| value.split(',')) {
           data.push(value_as::<T>(key, item)?);
         }
 
@@ -49,6 +66,25 @@ impl Parameters {
     }
   }
 
+  /// Inserts the parameters of `other`, keeping their order.
+  pub fn extend(&mut self, other: &Parameters) {
+    for (key, values) in other.iter() {
+      self.params.insert(key.clone(), values.clone());
+    }
+  }
+
+  /// Returns the parameters other than `keys`.
+  pub fn without(&self, keys: &[&str]) -> Parameters {
+    Parameters {
+      params: self
+        .params
+        .iter()
+        .filter(|(key, _)| !keys.contains(&key.as_str()))
+        .map(|(key, values)| (key.clone(), values.clone()))
+        .collect(),
+    }
+  }
+
   pub fn len(&self) -> usize {
     self.params.len()
   }
@@ -57,7 +93,7 @@ impl Parameters {
     self.params.is_empty()
   }
 
-  pub fn iter(&self) -> indexmap::map::Iter<'_, String, String> {
+  pub fn iter(&self) -> indexmap::map::Iter<'_, String, Vec<String>> {
     self.params.iter()
   }
 
@@ -70,21 +106,71 @@ impl Index<&str> for Parameters {
   type Output = String;
 
   fn index(&self, key: &str) -> &Self::Output {
-    self.params.get(key).unwrap()
+    self.get(key).unwrap()
   }
 }
 
 impl IntoIterator for Parameters {
-  type Item = (String, String);
-  type IntoIter = indexmap::map::IntoIter<String, String>;
+  type Item = (String, Vec<String>);
+  type IntoIter = indexmap::map::IntoIter<String, Vec<String>>;
 
   fn into_iter(self) -> Self::IntoIter {
     self.params.into_iter()
   }
 }
 
-fn value_as<T: FromStr>(key: &str, value: &str) -> Result<T, String> {
+/// Decodes the `^n`, `^'` and `^^` escapes of a parameter value as defined
+/// by RFC 6868. Other carets are kept as is.
+pub fn decode(value: &str) -> String {
+  let mut decoded = String::with_capacity(value.len());
+  let mut chars = value.chars();
+
+  while let Some(char) = chars.next() {
+    if char != '^' {
+      decoded.push(char);
+      continue;
+    }
+
+    match chars.next() {
+      Some('n') => decoded.push('\n'),
+      Some('\'') => decoded.push('"'),
+      Some('^') => decoded.push('^'),
+      Some(other) => {
+        decoded.push('^');
+        decoded.push(other);
+      }
+      None => decoded.push('^'),
+    }
+  }
+
+  decoded
+}
+
+/// Encodes the newlines, double quotes and carets of a parameter value as
+/// defined by RFC 6868. CRLF and lone CR are encoded as newlines too, as
+/// content lines can't hold them.
+pub fn encode(value: &str) -> String {
+  let mut encoded = String::with_capacity(value.len());
+  let mut chars = value.chars().peekable();
+
+  while let Some(char) = chars.next() {
+    match char {
+      '\r' => {
+        chars.next_if_eq(&'\n');
+        encoded.push_str("^n");
+      }
+      '\n' => encoded.push_str("^n"),
+      '"' => encoded.push_str("^'"),
+      '^' => encoded.push_str("^^"),
+      char => encoded.push(char),
+    }
+  }
+
+  encoded
+}
+
+fn value_as<T: FromStr>(key: &str, value: &str) -> Result<T, Error> {
   value
     .parse::<T>()
-    .map_err(|_| format!("Invalid {} value: {}", key, value))
+    .map_err(|_| Error::invalid_value(key, value))
 }
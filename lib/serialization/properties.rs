@@ -1,11 +1,21 @@
 use std::{fmt, str::FromStr};
 
-use super::property::{Error, Property};
+use super::property::{is_name, Property};
+use crate::error::Error;
+
+/// Maximum length of a content line in octets, excluding the line break.
+const MAX_LINE_LENGTH: usize = 75;
 
 pub struct Properties {
   items: Vec<Property>,
 }
 
+impl Default for Properties {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
 impl Properties {
   pub fn new() -> Self {
     Properties { items: Vec::new() }
@@ -15,21 +25,103 @@ impl Properties {
     self.items.push(property);
   }
 
+  /// Parses the content lines of `str`, unfolding the lines continued on the
+  /// next line started by a space or a tab. Errors of a folded line are
+  /// located at the line it starts at, with columns of the unfolded line.
+  ///
+  /// Lines indented as a whole, e.g. in template literals, are trimmed rather
+  /// than unfolded: a line continues the previous one only if it's indented
+  /// further, and not by several characters before the name of a property.
   pub fn from_str(str: &str) -> Result<Properties, Error> {
     let mut items = Vec::new();
 
-    for property_string in str.lines() {
-      let property_string = property_string.trim();
-
-      if property_string.is_empty() {
+    for (number, property_string) in unfold(str) {
+      if property_string.trim().is_empty() {
         continue;
       }
 
-      items.push(Property::from_string(property_string)?);
+      items.push(Property::parse(&property_string, number)?);
     }
 
     Ok(Properties { items })
   }
+
+  /// Same as `to_string`, but folds the content lines longer than 75 octets.
+  pub fn to_folded_string(&self) -> String {
+    self
+      .items
+      .iter()
+      .map(|property| fold(&property.to_string()))
+      .collect::<Vec<_>>()
+      .join("\n")
+  }
+
+  /// Same as `to_folded_string`, but terminates every line, including the
+  /// last one, with CRLF as required by RFC 5545 for iCalendar documents.
+  pub fn to_content_lines(&self) -> String {
+    self
+      .items
+      .iter()
+      .map(|property| fold(&property.to_string()).replace('\n', "\r\n") + "\r\n")
+      .collect()
+  }
+}
+
+/// Joins the folded content lines of `str`, returning each unfolded line along
+/// with the 1-based number of the line it starts at.
+fn unfold(str: &str) -> Vec<(usize, String)> {
+  // Unfolded lines along with the indentation of the line they start at
+  let mut lines: Vec<(usize, usize, String)> = Vec::new();
+
+  for (index, line) in str.lines().enumerate() {
+    let indent = line.len() - line.trim_start_matches([' ', '\t']).len();
+
+    match lines.last_mut() {
+      Some((_, base, unfolded)) if is_continuation(line, indent, *base, unfolded) => {
+        unfolded.push_str(&line[*base + 1..])
+      }
+      _ => lines.push((index + 1, indent, line.to_string())),
+    }
+  }
+
+  lines
+    .into_iter()
+    .map(|(number, _, line)| (number, line))
+    .collect()
+}
+
+/// Tells whether `line`, indented by `indent` characters, continues the
+/// unfolded line `unfolded` starting at a line indented by `base` characters.
+fn is_continuation(line: &str, indent: usize, base: usize, unfolded: &str) -> bool {
+  if indent <= base || line.trim().is_empty() || unfolded.trim().is_empty() {
+    return false;
+  }
+
+  // A fold is a single space or tab, a line indented further which starts a
+  // property is indented by hand
+  indent == base + 1
+    || !line[indent..]
+      .split_once([';', ':'])
+      .is_some_and(|(name, _)| is_name(name))
+}
+
+/// Splits `line` into lines of at most 75 octets, continuing each on the next
+/// line started by a space. Multi-octet characters are never split.
+fn fold(line: &str) -> String {
+  let mut folded = String::with_capacity(line.len());
+  let mut length = 0;
+
+  for char in line.chars() {
+    if length + char.len_utf8() > MAX_LINE_LENGTH {
+      folded.push_str("\n ");
+      length = 1;
+    }
+
+    folded.push(char);
+    length += char.len_utf8();
+  }
+
+  folded
 }
 
 impl IntoIterator for Properties {
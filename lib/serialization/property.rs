@@ -1,34 +1,28 @@
 use std::{fmt, str::FromStr};
 
-use super::parameters::Parameters;
+use indexmap::IndexMap;
+use itertools::Itertools;
 
+use super::parameters::{self, Parameters};
+use crate::error::{Error, Location};
+
+#[derive(Clone)]
 pub enum Value {
   Single(String),
   Parameters(Parameters),
 }
 
-#[derive(Debug)]
-#[allow(clippy::enum_variant_names)]
-pub enum Error {
-  InvalidProperty(String),
-  InvalidParameter(String),
-  InvalidParameters(String),
-}
-
-impl fmt::Display for Error {
-  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-    match self {
-      Error::InvalidProperty(str) => write!(f, "Invalid property: {}", str),
-      Error::InvalidParameter(str) => write!(f, "Invalid parameter: {}", str),
-      Error::InvalidParameters(str) => write!(f, "Invalid parameters: {}", str),
-    }
-  }
-}
-
+#[derive(Clone)]
 pub struct Property {
   name: String,
   parameters: Parameters,
   value: Value,
+  /// Location of the property in the parsed input.
+  location: Option<Location>,
+  /// Location of the value in the parsed input.
+  value_location: Option<Location>,
+  /// Locations of the parameters and value parts in the parsed input, by name.
+  locations: IndexMap<String, Location>,
 }
 
 impl Property {
@@ -44,80 +38,253 @@ impl Property {
     &self.value
   }
 
+  /// Returns the location of the property in the parsed input, if parsed.
+  pub fn location(&self) -> Option<&Location> {
+    self.location.as_ref()
+  }
+
   pub fn new(name: String, parameters: Parameters, value: Value) -> Property {
     Property {
       name,
       parameters,
       value,
+      location: None,
+      value_location: None,
+      locations: IndexMap::new(),
     }
   }
 
   pub fn from_string(str: &str) -> Result<Property, Error> {
-    let key_value: Vec<&str> = str.trim().split(':').collect();
+    Self::parse(str, 1)
+  }
 
-    if key_value.len() != 2 {
-      return Err(Error::InvalidProperty(str.to_string()));
+  /// Parses `str`, which is the line `number` of the input, following the
+  /// content line grammar of RFC 5545: parameter values may be quoted and hold
+  /// comma separated lists, and the first unquoted colon ends the parameters.
+  pub fn parse(str: &str, number: usize) -> Result<Property, Error> {
+    let line = Line { str, number };
+    let content = str.trim();
+    let (head, value_str) = match split_content(content) {
+      Some((head, value_str)) => (head, value_str.trim()),
+      None => return Err(Error::InvalidProperty(content.to_string()).at(line.locate(content))),
+    };
+
+    let name = head[0].trim();
+
+    if !is_name(name) {
+      return Err(Error::InvalidProperty(content.to_string()).at(line.locate(head[0])));
     }
 
-    let (name, parameters) = Self::parse_name(key_value[0].trim())?;
-    let value = Self::parse_parameters(key_value[1].trim())?;
+    let name = name.to_uppercase();
+    let mut parameters = Parameters::new();
+    let mut locations = IndexMap::new();
+
+    for param_string in head[1..].iter().map(|param_string| param_string.trim()) {
+      let (key, values) = Self::parse_parameter(&line, param_string)?;
 
-    if let Value::Single(_) = parameters {
-      return Err(Error::InvalidParameters(key_value[0].to_string()));
+      locations.insert(key.clone(), line.locate(param_string));
+      parameters.insert_values(key, values);
     }
 
-    match parameters {
-      Value::Parameters(parameters) => Ok(Property {
-        name,
-        parameters,
-        value,
-      }),
-      Value::Single(_) => Err(Error::InvalidParameters(key_value[0].to_string())),
+    let value = match name.as_str() {
+      "RRULE" | "EXRULE" => {
+        let (value, value_locations) = Self::parse_parts(&line, value_str)?;
+
+        locations.extend(value_locations);
+
+        value
+      }
+      _ => Value::Single(value_str.to_string()),
+    };
+
+    Ok(Property {
+      name,
+      parameters,
+      value,
+      location: Some(line.locate(content)),
+      value_location: Some(line.locate(value_str)),
+      locations,
+    })
+  }
+
+  /// Parses `str` as the value of the `name` property, e.g. a RRULE given
+  /// without the `RRULE:` prefix.
+  pub fn from_value(name: &str, str: &str) -> Result<Property, Error> {
+    let line = Line { str, number: 1 };
+    let content = str.trim();
+    let (value, locations) = Self::parse_parts(&line, content)?;
+
+    Ok(Property {
+      name: name.to_string(),
+      parameters: Parameters::new(),
+      value,
+      location: Some(line.locate(content)),
+      value_location: Some(line.locate(content)),
+      locations,
+    })
+  }
+
+  /// Attaches the location of the parameter or value `err` refers to, falling
+  /// back to the location of the whole property.
+  pub fn locate(&self, err: Error) -> Error {
+    let location = match &err {
+      Error::InvalidValue { name, .. } => self.locations.get(name),
+      Error::InvalidTimezone(_) => self.locations.get("TZID"),
+      Error::InvalidPropertyValue { .. } => self.value_location.as_ref(),
+      _ => None,
+    };
+
+    match location.or(self.location.as_ref()) {
+      Some(location) => err.at(location.clone()),
+      None => err,
     }
   }
 
-  fn parse_name(str: &str) -> Result<(String, Value), Error> {
-    let name_params: Vec<&str> = str.split(';').take(2).collect();
+  /// Parses a `NAME=VALUE[,VALUE...]` parameter, unquoting and decoding its
+  /// values.
+  fn parse_parameter(line: &Line, str: &str) -> Result<(String, Vec<String>), Error> {
+    let invalid = || Error::InvalidParameter(str.to_string()).at(line.locate(str));
+    let (key, mut rest) = str.split_once('=').ok_or_else(invalid)?;
+    let key = key.trim();
+
+    if !is_name(key) {
+      return Err(invalid());
+    }
+
+    let mut values = Vec::new();
 
-    let name = name_params[0].trim().to_uppercase();
-    let params = Self::parse_parameters(name_params.get(1).unwrap_or(&"").trim())?;
+    loop {
+      let (value, remainder) = match rest.trim_start().strip_prefix('"') {
+        Some(quoted) => {
+          let (value, remainder) = quoted.split_once('"').ok_or_else(invalid)?;
+
+          (value, remainder.trim_start())
+        }
+        None => {
+          let end = rest.find(',').unwrap_or(rest.len());
+          let value = rest[..end].trim();
+
+          if value.contains('"') {
+            return Err(invalid());
+          }
 
-    Ok((name, params))
+          (value, &rest[end..])
+        }
+      };
+
+      if value.chars().any(|char| char.is_control() && char != '\t') {
+        return Err(invalid());
+      }
+
+      values.push(parameters::decode(value));
+
+      rest = match remainder.strip_prefix(',') {
+        Some(remainder) => remainder,
+        None if remainder.is_empty() => break,
+        None => return Err(invalid()),
+      };
+    }
+
+    Ok((key.to_uppercase(), values))
   }
 
-  fn parse_parameters(str: &str) -> Result<Value, Error> {
+  /// Parses the `NAME=VALUE;...` parts of a recurrence rule.
+  fn parse_parts(line: &Line, str: &str) -> Result<(Value, IndexMap<String, Location>), Error> {
     let mut params = Parameters::new();
+    let mut locations = IndexMap::new();
     let param_strings = str.split(';');
 
     for param_string in param_strings {
       let param_string = param_string.trim();
-      let (key, value) = Self::parse_parameter(param_string)?;
+      let (key, value) = Self::parse_part(param_string);
 
       if let Some(name) = key {
-        params.insert(name.to_uppercase(), value.to_string());
+        locations.insert(name.clone(), line.locate(param_string));
+        params.insert(name, value.to_string());
       } else if params.is_empty() && !param_string.is_empty() {
-        return Ok(Value::Single(param_string.to_string()));
+        return Ok((Value::Single(param_string.to_string()), locations));
       } else if !params.is_empty() {
-        return Err(Error::InvalidParameters(str.to_string()));
+        return Err(Error::InvalidParameters(str.to_string()).at(line.locate(str)));
       }
     }
 
-    Ok(Value::Parameters(params))
+    Ok((Value::Parameters(params), locations))
+  }
+
+  /// Splits a rule part at its first `=`, so that values of unknown parts
+  /// may hold further ones, e.g. URLs.
+  fn parse_part(str: &str) -> (Option<String>, &str) {
+    match str.split_once('=') {
+      Some((key, value)) => (Some(key.trim().to_uppercase()), value.trim()),
+      None => (None, str.trim()),
+    }
   }
+}
 
-  fn parse_parameter(str: &str) -> Result<(Option<String>, &str), Error> {
-    let key_value: Vec<&str> = str.split('=').collect();
+/// Splits a content line into its name and parameters, and its value, at the
+/// first colon outside of a quoted parameter value.
+fn split_content(str: &str) -> Option<(Vec<&str>, &str)> {
+  let mut head = Vec::new();
+  let mut start = 0;
+  let mut quoted = false;
+
+  for (index, char) in str.char_indices() {
+    match char {
+      '"' => quoted = !quoted,
+      ';' if !quoted => {
+        head.push(&str[start..index]);
+        start = index + 1;
+      }
+      ':' if !quoted => {
+        head.push(&str[start..index]);
 
-    if key_value.len() == 1 {
-      return Ok((None, key_value[0].trim()));
-    } else if key_value.len() > 2 {
-      return Err(Error::InvalidParameter(str.to_string()));
+        return Some((head, &str[index + 1..]));
+      }
+      _ => {}
     }
+  }
+
+  None
+}
 
-    Ok((
-      Some(key_value[0].trim().to_uppercase()),
-      key_value[1].trim(),
-    ))
+/// Returns whether `str` is a valid property or parameter name.
+pub fn is_name(str: &str) -> bool {
+  !str.is_empty()
+    && str
+      .chars()
+      .all(|char| char.is_ascii_alphanumeric() || char == '-')
+}
+
+/// Encodes `value`, quoting it if it holds characters not allowed in unquoted
+/// parameter values.
+fn quote(value: &str) -> String {
+  let value = parameters::encode(value);
+
+  if value.contains([':', ';', ',']) {
+    format!("\"{}\"", value)
+  } else {
+    value
+  }
+}
+
+/// Line of the input being parsed.
+struct Line<'a> {
+  str: &'a str,
+  number: usize,
+}
+
+impl Line<'_> {
+  /// Returns the location of `token`, which must be a slice of the line.
+  fn locate(&self, token: &str) -> Location {
+    let offset = token.as_ptr() as usize - self.str.as_ptr() as usize;
+    let start = self.str[..offset].chars().count() + 1;
+
+    Location {
+      line: self.number,
+      start,
+      end: start + token.chars().count().saturating_sub(1),
+      token: token.to_string(),
+    }
   }
 }
 
@@ -133,22 +300,35 @@ impl fmt::Display for Property {
   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
     let mut string = self.name.to_string();
 
-    for (key, value) in self.parameters.iter() {
-      string.push_str(&format!(";{}={}", key, value));
+    for (key, values) in self.parameters.iter() {
+      string.push_str(&format!(
+        ";{}={}",
+        key,
+        values.iter().map(|value| quote(value)).join(",")
+      ));
     }
 
     string.push(':');
+    string.push_str(&self.value.to_string());
+
+    write!(f, "{}", string)
+  }
+}
+
+impl fmt::Display for Value {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let mut string = String::new();
 
-    match &self.value {
+    match self {
       Value::Single(value) => string.push_str(value),
       Value::Parameters(values) => {
         let mut values = values.iter();
 
         if let Some((key, value)) = values.next() {
-          string.push_str(&format!("{}={}", key, value));
+          string.push_str(&format!("{}={}", key, value.join(",")));
 
           for (key, value) in values {
-            string.push_str(&format!(";{}={}", key, value));
+            string.push_str(&format!(";{}={}", key, value.join(",")));
           }
         }
       }
@@ -0,0 +1,40 @@
+/// Unescapes a TEXT value as defined by RFC 5545, e.g. `\,` to `,` and `\n`
+/// to a newline.
+pub fn unescape(str: &str) -> String {
+  let mut unescaped = String::with_capacity(str.len());
+  let mut chars = str.chars();
+
+  while let Some(char) = chars.next() {
+    if char != '\\' {
+      unescaped.push(char);
+      continue;
+    }
+
+    match chars.next() {
+      Some('n' | 'N') => unescaped.push('\n'),
+      Some(other) => unescaped.push(other),
+      None => unescaped.push('\\'),
+    }
+  }
+
+  unescaped
+}
+
+/// Escapes a TEXT value as defined by RFC 5545, the inverse of `unescape`.
+pub fn escape(str: &str) -> String {
+  let mut escaped = String::with_capacity(str.len());
+
+  for char in str.chars() {
+    match char {
+      '\\' | ';' | ',' => {
+        escaped.push('\\');
+        escaped.push(char);
+      }
+      '\n' => escaped.push_str("\\n"),
+      '\r' => {}
+      _ => escaped.push(char),
+    }
+  }
+
+  escaped
+}
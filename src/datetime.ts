@@ -1,3 +1,10 @@
+/**
+ * Stands for the missing offset of a floating time in the arrays exchanged
+ * with the native module, out of the range of offsets so that any offset in
+ * seconds, including -1, can be told from it.
+ */
+const NO_OFFSET = -0x80000000;
+
 /**
  * Represents a date without time information.
  */
@@ -247,6 +254,48 @@ export class DateTime<T extends Time | undefined> {
     return DateTime.create(year, month, day, hour, minute, second, true);
   }
 
+  /**
+   * Creates a new DateTime object with time at a fixed offset from UTC, such
+   * as the one of an ISO 8601 timestamp.
+   *
+   * @param year - Year component (e.g., 2024)
+   * @param month - Month component (1-12)
+   * @param day - Day component (1-31)
+   * @param hour - Hour component (0-23)
+   * @param minute - Minute component (0-59)
+   * @param second - Second component (0-59)
+   * @param offset - Offset from UTC in seconds (e.g., 19800 for +05:30)
+   * @returns A DateTime instance with time at the offset
+   *
+   * @example
+   * ```typescript
+   * // 2024-01-15T14:30:00+05:30
+   * const value = DateTime.offset(2024, 1, 15, 14, 30, 0, 19800);
+   * console.log(value.toString()); // "20240115T143000+0530"
+   * console.log(value.toTimestamp()); // 1705309200000
+   * ```
+   */
+  public static offset(
+    year: number,
+    month: number,
+    day: number,
+    hour: number,
+    minute: number,
+    second: number,
+    offset: number,
+  ): DateTime<Time> {
+    const dt = new DateTime<Time>(year, month, day, {
+      hour,
+      minute,
+      second,
+      utc: offset === 0,
+    });
+
+    dt.offset = offset;
+
+    return dt;
+  }
+
   /**
    * Creates a new UTC DateTime object from a JavaScript Date object.
    *
@@ -352,6 +401,8 @@ export class DateTime<T extends Time | undefined> {
    * - `YYYYMMDD` - Date only (e.g., "20240115")
    * - `YYYYMMDDTHHMMSS` - Local date-time (e.g., "20240115T143000")
    * - `YYYYMMDDTHHMMSSZ` - UTC date-time (e.g., "20240115T143000Z")
+   * - `YYYYMMDDTHHMMSS±HHMM[SS]` - Date-time at a fixed offset from UTC
+   *   (e.g., "20240115T143000+0530")
    *
    * @param str - The RFC 5545 formatted string
    * @returns A DateTime instance
@@ -370,7 +421,7 @@ export class DateTime<T extends Time | undefined> {
    * ```
    */
   public static fromString(str: string): DateTime<Time> | DateTime<undefined> {
-    if (!(str.length === 8 || (str.length <= 16 && str.length >= 15))) {
+    if (![8, 15, 16, 20, 22].includes(str.length)) {
       throw new TypeError('Invalid date time string');
     }
 
@@ -396,6 +447,21 @@ export class DateTime<T extends Time | undefined> {
         throw new TypeError('Invalid time');
       }
 
+      if (str.length >= 20) {
+        const sign = str[15] === '+' ? 1 : str[15] === '-' ? -1 : NaN;
+        const offset =
+          sign *
+          (parseInt(str.slice(16, 18)) * 3600 +
+            parseInt(str.slice(18, 20)) * 60 +
+            (str.length === 22 ? parseInt(str.slice(20, 22)) : 0));
+
+        if (isNaN(offset)) {
+          throw new TypeError('Invalid offset');
+        }
+
+        return DateTime.offset(year, month, day, hour, minute, second, offset);
+      }
+
       return DateTime.create(year, month, day, hour, minute, second, utc);
     } else {
       return DateTime.create(year, month, day);
@@ -426,7 +492,7 @@ export class DateTime<T extends Time | undefined> {
         : undefined,
     ) as DT;
 
-    dt.offset = offset === -1 ? undefined : offset;
+    dt.offset = offset === NO_OFFSET ? undefined : offset;
 
     return dt;
   }
@@ -487,12 +553,12 @@ export class DateTime<T extends Time | undefined> {
         arr[offset + 3] = dt.time.hour;
         arr[offset + 4] = dt.time.minute;
         arr[offset + 5] = dt.time.second;
-        arr[offset + 6] = dt.offset ?? -1;
+        arr[offset + 6] = dt.offset ?? NO_OFFSET;
       } else {
         arr[offset + 3] = -1;
         arr[offset + 4] = -1;
         arr[offset + 5] = -1;
-        arr[offset + 6] = -1;
+        arr[offset + 6] = NO_OFFSET;
       }
     }
 
@@ -572,8 +638,14 @@ export class DateTime<T extends Time | undefined> {
    * Format depends on the DateTime type:
    * - `YYYYMMDD` for date only
    * - `YYYYMMDDTHHMMSSZ` for UTC date-time
+   * - `YYYYMMDDTHHMMSS±HHMM[SS]` for date-time at a fixed offset from UTC,
+   *   including occurrences of sets with a time zone
    * - `YYYYMMDDTHHMMSS` for local date-time
    *
+   * Since occurrences of sets with a time zone carry its offset, they are
+   * written with it, e.g. `20240115T090000-0500`, rather than in local time.
+   * Use `DateTime.local` with their components to get the wall-clock time.
+   *
    * @returns An RFC 5545 formatted string
    *
    * @example
@@ -600,7 +672,7 @@ export class DateTime<T extends Time | undefined> {
         this.time.hour.toString().padStart(2, '0') +
         this.time.minute.toString().padStart(2, '0') +
         this.time.second.toString().padStart(2, '0') +
-        (this.time.utc ? 'Z' : '');
+        (this.time.utc ? 'Z' : formatOffset(this.offset));
     }
 
     return str;
@@ -612,7 +684,8 @@ export class DateTime<T extends Time | undefined> {
    * This method requires timezone offset information to be available. The offset
    * is automatically set for:
    * - DateTime instances with `utc: true`
-   * - DateTime instances generated by RRule methods (`all`, `between`, or iteration)
+   * - DateTime instances generated by RRule methods (`all`, `between`, or iteration),
+   *   unless the set is floating (see `RRuleSet.resolveFloating`)
    *
    * @returns The Unix timestamp in milliseconds since January 1, 1970 00:00:00 UTC
    * @throws {Error} If timezone offset information is not available
@@ -677,7 +750,7 @@ export class DateTime<T extends Time | undefined> {
       this.time ? this.time.hour : -1,
       this.time ? this.time.minute : -1,
       this.time ? this.time.second : -1,
-      this.offset ?? -1,
+      this.offset ?? NO_OFFSET,
     ]);
   }
 
@@ -696,3 +769,20 @@ export class DateTime<T extends Time | undefined> {
     return time;
   }
 }
+
+function formatOffset(offset: number | undefined): string {
+  if (offset === undefined) {
+    return '';
+  }
+
+  const abs = Math.abs(offset);
+  const pad = (value: number) => value.toString().padStart(2, '0');
+  const seconds = abs % 60;
+
+  return (
+    (offset < 0 ? '-' : '+') +
+    pad(Math.floor(abs / 3600)) +
+    pad(Math.floor(abs / 60) % 60) +
+    (seconds ? pad(seconds) : '')
+  );
+}
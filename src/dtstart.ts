@@ -4,6 +4,7 @@ import {
   type DateTimeLike,
   type DateLike,
 } from './datetime';
+import { type UnknownParameter } from './unknown';
 
 /**
  * Options for creating a DtStart instance.
@@ -15,6 +16,8 @@ export interface DtStartOptions<
   value: DT;
   /** Optional timezone identifier (e.g., "America/New_York") */
   tzid?: string;
+  /** Parameters other than TZID and VALUE, preserved as parsed */
+  unknownParameters?: readonly UnknownParameter[];
 }
 
 /**
@@ -25,6 +28,8 @@ export interface DtStartLike<DT extends DateTimeLike | DateLike> {
   value: DT;
   /** Optional timezone identifier (e.g., "America/New_York") */
   tzid?: string;
+  /** Parameters other than TZID and VALUE, preserved as parsed */
+  unknownParameters?: readonly UnknownParameter[];
 }
 
 /**
@@ -55,6 +60,8 @@ export class DtStart<
   public readonly value: DT;
   /** Optional timezone identifier (e.g., "America/New_York") */
   public readonly tzid?: string;
+  /** Parameters other than TZID and VALUE, preserved as parsed */
+  public readonly unknownParameters?: readonly UnknownParameter[];
 
   public constructor(value: DT, tzid?: string);
   public constructor(options: DtStartOptions<DT>);
@@ -62,6 +69,7 @@ export class DtStart<
     if ('value' in valueOrOptions) {
       this.value = valueOrOptions.value;
       this.tzid = valueOrOptions.tzid;
+      this.unknownParameters = valueOrOptions.unknownParameters;
     } else {
       this.value = valueOrOptions;
       this.tzid = tzid;
@@ -95,6 +103,7 @@ export class DtStart<
     return new this({
       value: DateTime.fromPlain(plain.value),
       tzid: plain.tzid,
+      unknownParameters: plain.unknownParameters,
     });
   }
 
@@ -111,7 +120,11 @@ export class DtStart<
    * ```
    */
   public setTzid(tzid: string | undefined): DtStart<DT> {
-    return new DtStart(this.value, tzid);
+    return new DtStart({
+      value: this.value,
+      tzid,
+      unknownParameters: this.unknownParameters,
+    });
   }
 
   /**
@@ -129,7 +142,11 @@ export class DtStart<
   public setValue<NDT extends DateTime<Time> | DateTime<undefined>>(
     datetime: NDT,
   ): DtStart<NDT> {
-    return new DtStart(datetime, this.tzid);
+    return new DtStart({
+      value: datetime,
+      tzid: this.tzid,
+      unknownParameters: this.unknownParameters,
+    });
   }
 
   /**
@@ -157,6 +174,7 @@ export class DtStart<
     return {
       value: this.value.toPlain(),
       tzid: this.tzid,
+      unknownParameters: this.unknownParameters,
     };
   }
 }
@@ -0,0 +1,127 @@
+/**
+ * Stable identifiers of the errors raised while parsing and expanding
+ * recurrence rules. Unlike messages, codes are safe to branch on and to use as
+ * keys for localized messages.
+ */
+export type RRuleErrorCode =
+  | 'PARSE_ERROR'
+  | 'UNEXPECTED_PROPERTY'
+  | 'INVALID_VALUE'
+  | 'INVALID_TIMEZONE'
+  | 'VALUE_TYPE_MISMATCH'
+  | 'MISSING_PROPERTY'
+  | 'DUPLICATE_PROPERTY'
+  | 'MISSING_TIMEZONE'
+  | 'NONEXISTENT_LOCAL_TIME'
+  | 'AMBIGUOUS_LOCAL_TIME'
+  | 'VALIDATION_ERROR'
+  | 'INFINITE_SET';
+
+const codes: ReadonlySet<string> = new Set<RRuleErrorCode>([
+  'PARSE_ERROR',
+  'UNEXPECTED_PROPERTY',
+  'INVALID_VALUE',
+  'INVALID_TIMEZONE',
+  'VALUE_TYPE_MISMATCH',
+  'MISSING_PROPERTY',
+  'DUPLICATE_PROPERTY',
+  'MISSING_TIMEZONE',
+  'NONEXISTENT_LOCAL_TIME',
+  'AMBIGUOUS_LOCAL_TIME',
+  'VALIDATION_ERROR',
+  'INFINITE_SET',
+]);
+
+/**
+ * Position of the token which raised a parse error.
+ */
+export interface RRuleErrorLocation {
+  /**
+   * 1-based line of the token
+   */
+  readonly line: number;
+  /**
+   * 1-based column of the first character of the token
+   */
+  readonly start: number;
+  /**
+   * 1-based column of the last character of the token
+   */
+  readonly end: number;
+  /**
+   * The token itself, e.g. `BYMONTHDAY=32`
+   */
+  readonly token: string;
+}
+
+/**
+ * Error thrown when a recurrence rule or set can't be parsed, built or expanded.
+ *
+ * @example
+ * ```typescript
+ * try {
+ *   RRuleSet.fromString('DTSTART;TZID=Invalid:19970907T090000');
+ * } catch (error) {
+ *   if (error instanceof RRuleError && error.code === 'INVALID_TIMEZONE') {
+ *     // Ask for another time zone
+ *   }
+ * }
+ * ```
+ */
+export class RRuleError extends Error {
+  /**
+   * Stable identifier of the error kind.
+   */
+  public readonly code: RRuleErrorCode;
+
+  /**
+   * Location of the offending token when parsing a string.
+   */
+  public readonly location?: RRuleErrorLocation;
+
+  public constructor(
+    code: RRuleErrorCode,
+    message: string,
+    location?: RRuleErrorLocation,
+  ) {
+    super(message);
+
+    this.name = 'RRuleError';
+    this.code = code;
+    this.location = location;
+  }
+
+  /**
+   * Calls `fn`, converting the errors raised by the native module into `RRuleError`.
+   * @internal
+   */
+  public static wrap<T>(fn: () => T): T {
+    try {
+      return fn();
+    } catch (error) {
+      throw RRuleError.from(error);
+    }
+  }
+
+  private static from(error: unknown): unknown {
+    if (
+      error instanceof Error &&
+      'code' in error &&
+      typeof error.code === 'string' &&
+      codes.has(error.code)
+    ) {
+      const location =
+        'location' in error
+          ? (error.location as RRuleErrorLocation)
+          : undefined;
+
+      return new RRuleError(
+        error.code as RRuleErrorCode,
+        error.message,
+        location,
+      );
+    }
+
+    return error;
+  }
+}
@@ -4,7 +4,9 @@ import {
   type DateTimeLike,
   type DateLike,
 } from './datetime';
+import { RRuleError } from './error';
 import { ExDate as Rust } from './lib';
+import { nonEmpty, type UnknownParameter } from './unknown';
 
 /**
  * Options for creating an ExDate instance.
@@ -16,6 +18,8 @@ export interface ExDateOptions<
   values: DT[];
   /** Optional timezone identifier (e.g., "America/New_York") */
   tzid?: string;
+  /** Parameters other than TZID and VALUE, preserved as parsed */
+  unknownParameters?: readonly UnknownParameter[];
 }
 
 /**
@@ -26,6 +30,8 @@ export interface ExDateLike<DT extends DateTimeLike | DateLike> {
   values: DT[];
   /** Optional timezone identifier (e.g., "America/New_York") */
   tzid?: string;
+  /** Parameters other than TZID and VALUE, preserved as parsed */
+  unknownParameters?: readonly UnknownParameter[];
 }
 
 /**
@@ -60,6 +66,8 @@ export class ExDate<
   public readonly values: DT[];
   /** Optional timezone identifier (e.g., "America/New_York") */
   public readonly tzid?: string;
+  /** Parameters other than TZID and VALUE, preserved as parsed */
+  public readonly unknownParameters?: readonly UnknownParameter[];
 
   /** @internal */
   private rust?: Rust;
@@ -82,6 +90,7 @@ export class ExDate<
     } else {
       this.values = valueOrValuesOrOptions.values;
       this.tzid = valueOrValuesOrOptions.tzid;
+      this.unknownParameters = valueOrValuesOrOptions.unknownParameters;
     }
   }
 
@@ -91,10 +100,11 @@ export class ExDate<
   public static fromRust<DT extends DateTime<Time> | DateTime<undefined>>(
     rust: Rust,
   ): ExDate<DT> {
-    const rrule = new this(
-      DateTime.fromFlatInt32Array<DT>(rust.values),
-      rust.tzid ?? undefined,
-    );
+    const rrule = new this({
+      values: DateTime.fromFlatInt32Array<DT>(rust.values),
+      tzid: rust.tzid ?? undefined,
+      unknownParameters: nonEmpty(rust.unknownParameters),
+    });
 
     rrule.rust = rust;
 
@@ -131,6 +141,7 @@ export class ExDate<
     return new this({
       values: plain.values.map((dt) => DateTime.fromPlain(dt)),
       tzid: plain.tzid,
+      unknownParameters: plain.unknownParameters,
     });
   }
 
@@ -147,7 +158,11 @@ export class ExDate<
    * ```
    */
   public setTzid(tzid: string | undefined): ExDate<DT> {
-    return new ExDate(this.values, tzid);
+    return new ExDate({
+      values: this.values,
+      tzid,
+      unknownParameters: this.unknownParameters,
+    });
   }
 
   /**
@@ -168,7 +183,11 @@ export class ExDate<
   public setValues<NDT extends DateTime<Time> | DateTime<undefined>>(
     datetimes: NDT[],
   ): ExDate<NDT> {
-    return new ExDate(datetimes, this.tzid);
+    return new ExDate({
+      values: datetimes,
+      tzid: this.tzid,
+      unknownParameters: this.unknownParameters,
+    });
   }
 
   /**
@@ -199,14 +218,24 @@ export class ExDate<
     return {
       values: this.values.map((dt) => dt.toPlain()),
       tzid: this.tzid,
+      unknownParameters: this.unknownParameters,
     };
   }
 
   /**
+   * @param timezones - VTIMEZONE components of the set, resolving custom TZIDs
    * @internal
    */
-  public toRust(): Rust {
-    this.rust ??= new Rust(DateTime.toFlatInt32Array(this.values), this.tzid);
+  public toRust(timezones?: readonly string[]): Rust {
+    this.rust ??= RRuleError.wrap(
+      () =>
+        new Rust(
+          DateTime.toFlatInt32Array(this.values),
+          this.tzid,
+          this.unknownParameters,
+          timezones,
+        ),
+    );
 
     return this.rust;
   }
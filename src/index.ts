@@ -4,3 +4,5 @@ export * from './datetime';
 export * from './dtstart';
 export * from './exdate';
 export * from './rdate';
+export * from './error';
+export * from './unknown';
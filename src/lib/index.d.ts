@@ -1,24 +1,36 @@
 /* auto-generated by NAPI-RS */
 /* eslint-disable */
+export declare class CalendarComponent {
+  get name(): string
+  get uid(): string | null
+  get summary(): string | null
+  get components(): Array<string>
+  get rruleSet(): RRuleSet
+}
+
 export declare class ExDate {
-  constructor(dates: Int32Array, tzid?: string | undefined | null)
+  constructor(dates: Int32Array, tzid?: string | undefined | null, unknownParameters?: (readonly Parameter[]) | undefined | null, timezones?: (readonly string[]) | undefined | null, disambiguation?: 'reject' | 'earlier' | 'later' | 'shift-forward' | undefined | null)
   get values(): Int32Array
   get tzid(): string | null
+  get unknownParameters(): Array<Parameter>
 }
 
 export declare class RDate {
-  constructor(dates: Int32Array, tzid?: string | undefined | null)
+  constructor(dates: Int32Array, tzid?: string | undefined | null, unknownParameters?: (readonly Parameter[]) | undefined | null, timezones?: (readonly string[]) | undefined | null)
   get values(): Int32Array
   get tzid(): string | null
+  get unknownParameters(): Array<Parameter>
 }
 
 export declare class RRule {
-  constructor(frequency: Frequency, interval?: number | undefined | null, count?: number | undefined | null, weekstart?: Weekday | undefined | null, until?: Int32Array | undefined | null, byWeekday?: (readonly (NWeekday | Weekday)[]) | undefined | null, byHour?: (readonly number[]) | undefined | null, byMinute?: (readonly number[]) | undefined | null, bySecond?: (readonly number[]) | undefined | null, byMonthday?: (readonly number[]) | undefined | null, bySetpos?: (readonly number[]) | undefined | null, byMonth?: (readonly number[]) | undefined | null, byWeekno?: (readonly number[]) | undefined | null, byYearday?: (readonly number[]) | undefined | null)
+  constructor(frequency: Frequency, interval?: number | undefined | null, count?: number | undefined | null, weekstart?: Weekday | undefined | null, until?: Int32Array | undefined | null, byWeekday?: (readonly (NWeekday | Weekday)[]) | undefined | null, byHour?: (readonly number[]) | undefined | null, byMinute?: (readonly number[]) | undefined | null, bySecond?: (readonly number[]) | undefined | null, byMonthday?: (readonly number[]) | undefined | null, bySetpos?: (readonly number[]) | undefined | null, byMonth?: (readonly number[]) | undefined | null, byWeekno?: (readonly number[]) | undefined | null, byYearday?: (readonly number[]) | undefined | null, unknownParameters?: (readonly Parameter[]) | undefined | null, unknownParts?: (readonly Parameter[]) | undefined | null)
   static parse(str: string): RRule
   get frequency(): Frequency
   get interval(): number | null
   get count(): number | null
   get byWeekday(): NWeekday[]
+  get unknownParameters(): Array<Parameter>
+  get unknownParts(): Array<Parameter>
   get byHour(): Array<number>
   get byMinute(): Array<number>
   get bySecond(): Array<number>
@@ -29,23 +41,49 @@ export declare class RRule {
   get byYearday(): Array<number>
   get weekstart(): Weekday | null
   get until(): Int32Array | null
+  validate(): Array<Diagnostic>
+  lint(dtstart?: Int32Array | undefined | null, tzid?: string | undefined | null): Array<Diagnostic>
   toString(): string
 }
 
 export declare class RRuleSet {
-  constructor(dtstart: Int32Array, tzid?: string | undefined | null, dtstartValue?: string | undefined | null, rrules?: (readonly RRule[]) | undefined | null, exrules?: (readonly RRule[]) | undefined | null, exdates?: (readonly ExDate[]) | undefined | null, rdates?: (readonly RDate[]) | undefined | null)
+  constructor(dtstart: Int32Array, tzid?: string | undefined | null, dtstartValue?: string | undefined | null, rrules?: (readonly RRule[]) | undefined | null, exrules?: (readonly RRule[]) | undefined | null, exdates?: (readonly ExDate[]) | undefined | null, rdates?: (readonly RDate[]) | undefined | null, dtstartUnknownParameters?: (readonly Parameter[]) | undefined | null, unknownProperties?: (readonly Property[]) | undefined | null, timezones?: (readonly string[]) | undefined | null)
   get tzid(): string | null
   get dtstart(): Int32Array
+  get dtstartUnknownParameters(): Array<Parameter>
+  get unknownProperties(): Array<Property>
+  get timezones(): Array<string>
+  get disambiguation(): 'reject' | 'earlier' | 'later' | 'shift-forward'
   get rrules(): RRule[]
   get exrules(): RRule[]
   get exdates(): ExDate[]
   get rdates(): RDate[]
   static parse(str: string): RRuleSet
+  static parseCalendar(str: string): CalendarComponent[]
   all(limit?: number | undefined | null): Int32Array
+  allInTimezone(tzid: string, limit?: number | undefined | null): Int32Array
   between(afterDatetime: Int32Array, beforeDatetime: Int32Array, inclusive?: boolean | undefined | null): Int32Array
+  betweenInTimezone(tzid: string, afterDatetime: Int32Array, beforeDatetime: Int32Array, inclusive?: boolean | undefined | null): Int32Array
+  allTimestamps(limit?: number | undefined | null): Float64Array
+  betweenTimestamps(afterDatetime: Int32Array, beforeDatetime: Int32Array, inclusive?: boolean | undefined | null): Float64Array
+  after(datetime: Int32Array, inclusive?: boolean | undefined | null): Int32Array | null
+  before(datetime: Int32Array, inclusive?: boolean | undefined | null): Int32Array | null
+  includes(datetime: Int32Array): boolean
+  count(): number
+  extent(): Extent
+  countBetween(afterDatetime: Int32Array, beforeDatetime: Int32Array, inclusive?: boolean | undefined | null): number
+  nth(index: number): Int32Array | null
+  indexOf(datetime: Int32Array): number | null
   setFromString(str: string): this
-  toString(): string
+  toString(fold?: boolean | undefined | null): string
+  toCalendar(uid?: string | undefined | null, summary?: string | undefined | null, dtend?: Int32Array | undefined | null, duration?: string | undefined | null, dtstamp?: Int32Array | undefined | null, components?: (readonly string[]) | undefined | null): string
+  resolveFloating(tzid: string): RRuleSet
+  withTimezone(tzid: string, preserve: 'instant' | 'wall-clock'): RRuleSet
   iterator(skip?: number | undefined | null): RRuleSetIterator
+  iteratorInTimezone(tzid: string): RRuleSetIterator
+  timestampIterator(): RRuleSetTimestampIterator
+  iteratorFrom(datetime: Int32Array, inclusive?: boolean | undefined | null): RRuleSetIterator
+  reverseIteratorFrom(datetime: Int32Array, inclusive?: boolean | undefined | null): RRuleSetIterator
 }
 
 export declare class RRuleSetIterator {
@@ -53,6 +91,32 @@ export declare class RRuleSetIterator {
   next(): Int32Array | null
 }
 
+export declare class RRuleSetTimestampIterator {
+  next(size: number): Float64Array
+}
+
+export interface Diagnostic {
+  severity: 'error' | 'warning'
+  code: string
+  message: string
+}
+
+/**
+ * Position of the token which raised a parse error. Lines and columns are
+ * 1-based, `end` is the column of the last character of the token.
+ */
+export interface ErrorLocation {
+  line: number
+  start: number
+  end: number
+  token: string
+}
+
+export interface Extent {
+  finite: boolean
+  last?: Int32Array
+}
+
 export declare enum Frequency {
   Yearly = 0,
   Monthly = 1,
@@ -83,6 +147,22 @@ export interface NWeekday {
   weekday: Weekday
 }
 
+/**
+ * Parameter, or recurrence rule part, which is not interpreted but kept as
+ * parsed.
+ */
+export interface Parameter {
+  name: string
+  values: readonly string[]
+}
+
+/** Property which is not interpreted but kept as parsed. */
+export interface Property {
+  name: string
+  parameters: readonly Parameter[]
+  value: string
+}
+
 export declare enum Weekday {
   Monday = 0,
   Tuesday = 1,
@@ -4,7 +4,9 @@ import {
   type DateTimeLike,
   type DateLike,
 } from './datetime';
+import { RRuleError } from './error';
 import { RDate as Rust } from './lib';
+import { nonEmpty, type UnknownParameter } from './unknown';
 
 /**
  * Options for creating an RDate instance.
@@ -14,6 +16,8 @@ export interface RDateOptions<DT extends DateTime<Time> | DateTime<undefined>> {
   values: DT[];
   /** Optional timezone identifier (e.g., "America/New_York") */
   tzid?: string;
+  /** Parameters other than TZID and VALUE, preserved as parsed */
+  unknownParameters?: readonly UnknownParameter[];
 }
 
 /**
@@ -24,6 +28,8 @@ export interface RDateLike<DT extends DateTimeLike | DateLike> {
   values: DT[];
   /** Optional timezone identifier (e.g., "America/New_York") */
   tzid?: string;
+  /** Parameters other than TZID and VALUE, preserved as parsed */
+  unknownParameters?: readonly UnknownParameter[];
 }
 
 /**
@@ -56,6 +62,8 @@ export class RDate<DT extends DateTime<Time> | DateTime<undefined>> {
   public readonly values: DT[];
   /** Optional timezone identifier (e.g., "America/New_York") */
   public readonly tzid?: string;
+  /** Parameters other than TZID and VALUE, preserved as parsed */
+  public readonly unknownParameters?: readonly UnknownParameter[];
 
   /** @internal */
   private rust?: Rust;
@@ -78,6 +86,7 @@ export class RDate<DT extends DateTime<Time> | DateTime<undefined>> {
     } else {
       this.values = valueOrValuesOrOptions.values;
       this.tzid = valueOrValuesOrOptions.tzid;
+      this.unknownParameters = valueOrValuesOrOptions.unknownParameters;
     }
   }
 
@@ -87,10 +96,11 @@ export class RDate<DT extends DateTime<Time> | DateTime<undefined>> {
   public static fromRust<DT extends DateTime<Time> | DateTime<undefined>>(
     rust: Rust,
   ): RDate<DT> {
-    const rrule = new this(
-      DateTime.fromFlatInt32Array<DT>(rust.values),
-      rust.tzid ?? undefined,
-    );
+    const rrule = new this({
+      values: DateTime.fromFlatInt32Array<DT>(rust.values),
+      tzid: rust.tzid ?? undefined,
+      unknownParameters: nonEmpty(rust.unknownParameters),
+    });
 
     rrule.rust = rust;
 
@@ -127,6 +137,7 @@ export class RDate<DT extends DateTime<Time> | DateTime<undefined>> {
     return new this({
       values: plain.values.map((dt) => DateTime.fromPlain(dt)),
       tzid: plain.tzid,
+      unknownParameters: plain.unknownParameters,
     });
   }
 
@@ -143,7 +154,11 @@ export class RDate<DT extends DateTime<Time> | DateTime<undefined>> {
    * ```
    */
   public setTzid(tzid: string | undefined): RDate<DT> {
-    return new RDate(this.values, tzid);
+    return new RDate({
+      values: this.values,
+      tzid,
+      unknownParameters: this.unknownParameters,
+    });
   }
 
   /**
@@ -164,7 +179,11 @@ export class RDate<DT extends DateTime<Time> | DateTime<undefined>> {
   public setValues<NDT extends DateTime<Time> | DateTime<undefined>>(
     datetimes: NDT[],
   ): RDate<NDT> {
-    return new RDate(datetimes, this.tzid);
+    return new RDate({
+      values: datetimes,
+      tzid: this.tzid,
+      unknownParameters: this.unknownParameters,
+    });
   }
 
   /**
@@ -195,14 +214,24 @@ export class RDate<DT extends DateTime<Time> | DateTime<undefined>> {
     return {
       values: this.values.map((dt) => dt.toPlain()),
       tzid: this.tzid,
+      unknownParameters: this.unknownParameters,
     };
   }
 
   /**
+   * @param timezones - VTIMEZONE components of the set, resolving custom TZIDs
    * @internal
    */
-  public toRust(): Rust {
-    this.rust ??= new Rust(DateTime.toFlatInt32Array(this.values), this.tzid);
+  public toRust(timezones?: readonly string[]): Rust {
+    this.rust ??= RRuleError.wrap(
+      () =>
+        new Rust(
+          DateTime.toFlatInt32Array(this.values),
+          this.tzid,
+          this.unknownParameters,
+          timezones,
+        ),
+    );
 
     return this.rust;
   }
@@ -1,5 +1,9 @@
 import { RRule, type RRuleLike } from './rrule';
-import { type RRuleSetIterator, RRuleSet as Rust } from './lib';
+import {
+  type RRuleSetIterator,
+  type RRuleSetTimestampIterator,
+  RRuleSet as Rust,
+} from './lib';
 import {
   type Time,
   DateTime,
@@ -7,8 +11,10 @@ import {
   type DateLike,
 } from './datetime';
 import { DtStart, type DtStartLike } from './dtstart';
+import { RRuleError } from './error';
 import { ExDate, type ExDateLike } from './exdate';
 import { RDate, type RDateLike } from './rdate';
+import { nonEmpty, type UnknownProperty } from './unknown';
 import { OperationCache } from './cache';
 
 /**
@@ -61,6 +67,31 @@ export interface RRuleSetCache {
   enable(): void;
 }
 
+/**
+ * How wall-clock times falling into a DST gap or overlap are resolved, akin
+ * to the `disambiguation` option of Temporal:
+ * - `reject`: such DTSTART, UNTIL, EXDATE and RDATE values raise an error.
+ *   Generated occurrences are resolved as by `shift-forward`, as RFC 5545
+ *   requires.
+ * - `earlier`: the earlier time of an overlap, times in a gap are moved back
+ *   by its length.
+ * - `later`: the later time of an overlap, times in a gap are moved forward
+ *   by its length.
+ * - `shift-forward`: the earlier time of an overlap, times in a gap are moved
+ *   forward by its length.
+ */
+export type Disambiguation = 'reject' | 'earlier' | 'later' | 'shift-forward';
+
+/**
+ * What {@link RRuleSet.withTimezone} keeps when moving a set into another time
+ * zone:
+ * - `instant`: the instants of the occurrences, their wall-clock times change
+ *   along with the offset.
+ * - `wall-clock`: the wall-clock times of the occurrences, e.g. a standup at
+ *   9am stays at 9am, their instants change along with the offset.
+ */
+export type Preserve = 'instant' | 'wall-clock';
+
 /**
  * Options for creating an RRuleSet instance.
  */
@@ -79,6 +110,12 @@ export interface RRuleSetOptions<
   readonly exdates?: readonly ExDate<DT>[];
   /** Array of recurrence dates to include */
   readonly rdates?: readonly RDate<DT>[];
+  /** Properties which are not interpreted, preserved as parsed */
+  readonly unknownProperties?: readonly UnknownProperty[];
+  /** VTIMEZONE components defining the TZIDs which name no IANA time zone */
+  readonly timezones?: readonly string[];
+  /** Resolution of times in DST gaps and overlaps, `reject` by default */
+  readonly disambiguation?: Disambiguation;
 }
 
 /**
@@ -95,6 +132,71 @@ export interface RRuleSetLike<DT extends DateTimeLike | DateLike> {
   readonly exdates: readonly ExDateLike<DT>[];
   /** Array of recurrence dates to include */
   readonly rdates: readonly RDateLike<DT>[];
+  /** Properties which are not interpreted, preserved as parsed */
+  readonly unknownProperties?: readonly UnknownProperty[];
+  /** VTIMEZONE components defining the TZIDs which name no IANA time zone */
+  readonly timezones?: readonly string[];
+  /** Resolution of times in DST gaps and overlaps, `reject` by default */
+  readonly disambiguation?: Disambiguation;
+}
+
+/**
+ * Describes how far an RRuleSet extends.
+ *
+ * A set is finite when every RRULE is limited by COUNT or UNTIL, in which case
+ * the number of occurrences and the last one are known. The last one is looked
+ * up backwards from the end of the set, while the number of occurrences is
+ * computed on first access, as it may take iterating the whole set.
+ */
+export type RRuleSetExtent<DT extends DateTime<Time> | DateTime<undefined>> =
+  | {
+      readonly finite: false;
+    }
+  | {
+      readonly finite: true;
+      /** Total number of occurrences, computed on first access */
+      readonly count: number;
+      /** The last occurrence, or `null` if the set has no occurrences */
+      readonly last: DT | null;
+    };
+
+/**
+ * Recurrence set of a VEVENT, VTODO or VJOURNAL component of an iCalendar
+ * document.
+ */
+export interface RRuleSetComponent<
+  DT extends DateTime<Time> | DateTime<undefined>,
+> {
+  /** Name of the component */
+  readonly component: 'VEVENT' | 'VTODO' | 'VJOURNAL';
+  /** Unique identifier of the component (UID property) */
+  readonly uid?: string;
+  /** Unescaped SUMMARY of the component */
+  readonly summary?: string;
+  /** Recurrence set defined by the component */
+  readonly rruleSet: RRuleSet<DT>;
+  /** Nested components, such as VALARM, as iCalendar text */
+  readonly components?: readonly string[];
+}
+
+/**
+ * Properties of the VEVENT written by {@link RRuleSet.toCalendar}.
+ */
+export interface RRuleSetCalendarOptions<
+  DT extends DateTime<Time> | DateTime<undefined>,
+> {
+  /** Unique identifier of the event, generated if omitted */
+  readonly uid?: string;
+  /** Summary of the event, escaped as TEXT */
+  readonly summary?: string;
+  /** End of the first occurrence, in the time zone of DTSTART */
+  readonly dtend?: DT;
+  /** Duration of each occurrence, e.g. `PT1H30M`. Excludes `dtend` */
+  readonly duration?: string;
+  /** Creation time of the document in UTC, the current time if omitted */
+  readonly dtstamp?: DateTime<Time>;
+  /** Components nested in the event, such as VALARM, as iCalendar text */
+  readonly components?: readonly string[];
 }
 
 /**
@@ -145,6 +247,12 @@ export class RRuleSet<
   public readonly exdates: readonly ExDate<DT>[];
   /** Array of recurrence dates to include */
   public readonly rdates: readonly RDate<DT>[];
+  /** Properties which are not interpreted, preserved as parsed */
+  public readonly unknownProperties?: readonly UnknownProperty[];
+  /** VTIMEZONE components defining the TZIDs which name no IANA time zone */
+  public readonly timezones?: readonly string[];
+  /** Resolution of times in DST gaps and overlaps, `reject` by default */
+  public readonly disambiguation?: Disambiguation;
 
   private _cache: OperationCache = new OperationCache({
     disabled: false,
@@ -164,6 +272,9 @@ export class RRuleSet<
       this.exrules = optionsOrDtstart?.exrules ?? [];
       this.exdates = optionsOrDtstart?.exdates ?? [];
       this.rdates = optionsOrDtstart?.rdates ?? [];
+      this.unknownProperties = optionsOrDtstart?.unknownProperties;
+      this.timezones = optionsOrDtstart?.timezones;
+      this.disambiguation = optionsOrDtstart?.disambiguation;
     } else {
       this.dtstart = optionsOrDtstart;
       this.rrules = [];
@@ -204,6 +315,9 @@ export class RRuleSet<
   /**
    * Parses an RFC 5545 formatted string into an RRuleSet.
    *
+   * TZIDs which don't name an IANA time zone are resolved through the
+   * VTIMEZONE components of the string, which are kept in `timezones`.
+   *
    * @param str - RFC 5545 formatted string containing DTSTART, RRULE, etc.
    * @returns A new RRuleSet instance
    *
@@ -218,7 +332,42 @@ export class RRuleSet<
   public static fromString<DT extends DateTime<Time> | DateTime<undefined>>(
     str: string,
   ): RRuleSet<DT> {
-    return this.fromRust(Rust.parse(str));
+    return this.fromRust(RRuleError.wrap(() => Rust.parse(str)));
+  }
+
+  /**
+   * Parses the VEVENT, VTODO and VJOURNAL components of an iCalendar document,
+   * such as the content of an .ics file, into one RRuleSet per component.
+   * Components without DTSTART are skipped.
+   *
+   * @param str - iCalendar document, with or without the VCALENDAR wrapper
+   * @returns The recurrence sets along with the components defining them
+   *
+   * @example
+   * ```typescript
+   * const [event] = RRuleSet.fromCalendar(`BEGIN:VCALENDAR
+   * BEGIN:VEVENT
+   * UID:standup@example.com
+   * SUMMARY:Standup
+   * DTSTART:20240115T090000Z
+   * RRULE:FREQ=DAILY;COUNT=5
+   * END:VEVENT
+   * END:VCALENDAR`);
+   * console.log(event.uid, event.rruleSet.all());
+   * ```
+   */
+  public static fromCalendar<DT extends DateTime<Time> | DateTime<undefined>>(
+    str: string,
+  ): RRuleSetComponent<DT>[] {
+    const components = RRuleError.wrap(() => Rust.parseCalendar(str));
+
+    return components.map((component) => ({
+      component: component.name as RRuleSetComponent<DT>['component'],
+      uid: component.uid ?? undefined,
+      summary: component.summary ?? undefined,
+      rruleSet: this.fromRust<DT>(component.rruleSet),
+      components: nonEmpty(component.components),
+    }));
   }
 
   /**
@@ -254,6 +403,9 @@ export class RRuleSet<
       exrules: plain.exrules.map((rrule) => RRule.fromPlain(rrule)),
       exdates: plain.exdates.map((datetime) => ExDate.fromPlain(datetime)),
       rdates: plain.rdates.map((datetime) => RDate.fromPlain(datetime)),
+      unknownProperties: plain.unknownProperties,
+      timezones: plain.timezones,
+      disambiguation: plain.disambiguation,
     });
   }
 
@@ -267,11 +419,16 @@ export class RRuleSet<
       dtstart: new DtStart<DT>({
         value: DateTime.fromInt32Array<DT>(rust.dtstart),
         tzid: rust.tzid ?? undefined,
+        unknownParameters: nonEmpty(rust.dtstartUnknownParameters),
       }),
       rrules: rust.rrules.map((rrule) => RRule.fromRust<DT>(rrule)),
       exrules: rust.exrules.map((rrule) => RRule.fromRust<DT>(rrule)),
       exdates: rust.exdates.map((exdate) => ExDate.fromRust<DT>(exdate)),
       rdates: rust.rdates.map((rdate) => RDate.fromRust<DT>(rdate)),
+      unknownProperties: nonEmpty(rust.unknownProperties),
+      timezones: nonEmpty(rust.timezones),
+      disambiguation:
+        rust.disambiguation === 'reject' ? undefined : rust.disambiguation,
     });
 
     set.rust = rust;
@@ -459,6 +616,28 @@ export class RRuleSet<
     });
   }
 
+  /**
+   * Creates a new RRuleSet resolving wall-clock times in DST gaps and
+   * overlaps by a different policy.
+   *
+   * @param disambiguation - The new policy
+   * @returns A new RRuleSet instance
+   *
+   * @example
+   * ```typescript
+   * const rruleSet = RRuleSet.fromString(
+   *   'DTSTART;TZID=Europe/Berlin:20240331T023000\nRRULE:FREQ=DAILY;COUNT=2',
+   * ).setDisambiguation('shift-forward');
+   * rruleSet.all(); // 20240331T033000+0200, 20240401T023000+0200
+   * ```
+   */
+  public setDisambiguation(disambiguation: Disambiguation): RRuleSet<DT> {
+    return new RRuleSet({
+      ...this.toOptions(),
+      disambiguation,
+    });
+  }
+
   /**
    * Returns all the occurrences of the recurrence set.
    *
@@ -483,7 +662,39 @@ export class RRuleSet<
   // TODO: add skip (?)
   public all(limit?: number): readonly DT[] {
     return this._cache.getOrCompute<DT[]>(`all:${limit}`, () =>
-      DateTime.fromFlatInt32Array(this.toRust().all(limit)),
+      DateTime.fromFlatInt32Array(
+        RRuleError.wrap(() => this.toRust().all(limit)),
+      ),
+    );
+  }
+
+  /**
+   * Returns all occurrences converted into the given time zone, keeping
+   * their instants. Dates are returned as is.
+   *
+   * Floating sets can't be converted, see {@link RRuleSet.resolveFloating}.
+   *
+   * @param tzid - IANA or Windows time zone to convert the occurrences into
+   * @param limit - Optional maximum number of occurrences to return
+   * @returns Array of date/time occurrences with the offsets of `tzid`
+   *
+   * @example
+   * ```typescript
+   * const rruleSet = new RRuleSet({
+   *   dtstart: new DtStart(DateTime.local(2024, 1, 15, 9, 0, 0), 'Europe/Paris'),
+   *   rrules: [new RRule({ frequency: Frequency.Daily, count: 5 })]
+   * });
+   *
+   * const tokyo = rruleSet.allInTimezone('Asia/Tokyo'); // 20240115T170000+0900, ...
+   * ```
+   */
+  public allInTimezone(tzid: string, limit?: number): readonly DT[] {
+    return this._cache.getOrCompute<DT[]>(
+      `allInTimezone:${tzid},${limit}`,
+      () =>
+        DateTime.fromFlatInt32Array(
+          RRuleError.wrap(() => this.toRust().allInTimezone(tzid, limit)),
+        ),
     );
   }
 
@@ -521,7 +732,259 @@ export class RRuleSet<
       `between:${after.toString()},${before.toString()},${inclusive}`,
       () =>
         DateTime.fromFlatInt32Array(
-          this.toRust().between(
+          RRuleError.wrap(() =>
+            this.toRust().between(
+              after.toInt32Array(),
+              before.toInt32Array(),
+              inclusive,
+            ),
+          ),
+        ),
+    );
+  }
+
+  /**
+   * Returns all occurrences between two dates, converted into the given time
+   * zone. The bounds are given in the time zone of the set.
+   *
+   * @param tzid - IANA or Windows time zone to convert the occurrences into
+   * @param after - The lower bound date (exclusive by default)
+   * @param before - The upper bound date (exclusive by default)
+   * @param inclusive - Whether to include the boundary dates in results
+   * @returns Array of date/time occurrences in the range
+   *
+   * @example
+   * ```typescript
+   * const january = rruleSet.betweenInTimezone(
+   *   'America/New_York',
+   *   DateTime.local(2024, 1, 1, 0, 0, 0),
+   *   DateTime.local(2024, 2, 1, 0, 0, 0)
+   * );
+   * ```
+   */
+  public betweenInTimezone(
+    tzid: string,
+    after: DT,
+    before: DT,
+    inclusive?: boolean,
+  ): readonly DT[] {
+    return this._cache.getOrCompute(
+      `betweenInTimezone:${tzid},${after.toString()},${before.toString()},${inclusive}`,
+      () =>
+        DateTime.fromFlatInt32Array(
+          RRuleError.wrap(() =>
+            this.toRust().betweenInTimezone(
+              tzid,
+              after.toInt32Array(),
+              before.toInt32Array(),
+              inclusive,
+            ),
+          ),
+        ),
+    );
+  }
+
+  /**
+   * Returns all occurrences as UTC epoch milliseconds, without building
+   * DateTime objects. Dates stand for midnight in the time zone of the set.
+   *
+   * Floating sets can't be converted, see {@link RRuleSet.resolveFloating}.
+   * Results are not cached.
+   *
+   * @param limit - Optional maximum number of occurrences to return
+   * @returns Timestamps of the occurrences
+   *
+   * @example
+   * ```typescript
+   * const rruleSet = new RRuleSet({
+   *   dtstart: new DtStart(DateTime.utc(2024, 1, 15, 9, 0, 0)),
+   *   rrules: [new RRule({ frequency: Frequency.Daily, count: 5 })]
+   * });
+   *
+   * const timestamps = rruleSet.allTimestamps(); // 1705309200000, ...
+   * ```
+   */
+  public allTimestamps(limit?: number): Float64Array {
+    return RRuleError.wrap(() => this.toRust().allTimestamps(limit));
+  }
+
+  /**
+   * Returns all occurrences between two dates as UTC epoch milliseconds.
+   * Results are not cached.
+   *
+   * @param after - The lower bound date (exclusive by default)
+   * @param before - The upper bound date (exclusive by default)
+   * @param inclusive - Whether to include the boundary dates in results
+   * @returns Timestamps of the occurrences in the range
+   *
+   * @example
+   * ```typescript
+   * const january = rruleSet.betweenTimestamps(
+   *   DateTime.utc(2024, 1, 1, 0, 0, 0),
+   *   DateTime.utc(2024, 2, 1, 0, 0, 0)
+   * );
+   * ```
+   */
+  public betweenTimestamps(
+    after: DT,
+    before: DT,
+    inclusive?: boolean,
+  ): Float64Array {
+    return RRuleError.wrap(() =>
+      this.toRust().betweenTimestamps(
+        after.toInt32Array(),
+        before.toInt32Array(),
+        inclusive,
+      ),
+    );
+  }
+
+  /**
+   * Returns the first occurrence after the given date.
+   *
+   * Unlike iterating the set, this does not enumerate occurrences from
+   * DTSTART, so it stays fast for long-running series.
+   *
+   * @param datetime - The date to search after
+   * @param inclusive - Whether an occurrence equal to `datetime` is returned
+   * @returns The next occurrence, or `null` if there is none
+   *
+   * @example
+   * ```typescript
+   * const rruleSet = new RRuleSet({
+   *   dtstart: new DtStart(DateTime.date(2015, 1, 1)),
+   *   rrules: [new RRule(Frequency.Daily)]
+   * });
+   *
+   * const next = rruleSet.after(DateTime.date(2024, 6, 1)); // 2024-06-02
+   * const same = rruleSet.after(DateTime.date(2024, 6, 1), true); // 2024-06-01
+   * ```
+   */
+  public after(datetime: DT, inclusive?: boolean): DT | null {
+    return this._cache.getOrCompute(
+      `after:${datetime.toString()},${inclusive}`,
+      () => {
+        const result = RRuleError.wrap(() =>
+          this.toRust().after(datetime.toInt32Array(), inclusive),
+        );
+
+        return result ? DateTime.fromInt32Array<DT>(result) : null;
+      },
+    );
+  }
+
+  /**
+   * Returns whether the given date is an occurrence of the set.
+   *
+   * RDATE, EXDATE and EXRULE are taken into account, and the occurrences
+   * preceding `datetime` are not enumerated.
+   *
+   * @param datetime - The date to check
+   * @returns `true` if the set produces `datetime`
+   *
+   * @example
+   * ```typescript
+   * const rruleSet = new RRuleSet({
+   *   dtstart: new DtStart(DateTime.date(2015, 1, 1)),
+   *   rrules: [new RRule(Frequency.Weekly)]
+   * });
+   *
+   * rruleSet.includes(DateTime.date(2024, 6, 6)); // true
+   * rruleSet.includes(DateTime.date(2024, 6, 7)); // false
+   * ```
+   */
+  public includes(datetime: DT): boolean {
+    return this._cache.getOrCompute(`includes:${datetime.toString()}`, () =>
+      RRuleError.wrap(() => this.toRust().includes(datetime.toInt32Array())),
+    );
+  }
+
+  /**
+   * Returns the number of occurrences of a finite set.
+   *
+   * @returns The number of occurrences
+   * @throws If an RRULE is limited by neither COUNT nor UNTIL
+   *
+   * @example
+   * ```typescript
+   * const rruleSet = new RRuleSet({
+   *   dtstart: new DtStart(DateTime.date(2024, 1, 1)),
+   *   rrules: [new RRule({ frequency: Frequency.Daily, count: 40 })]
+   * });
+   *
+   * rruleSet.count(); // 40
+   * ```
+   */
+  public count(): number {
+    return this._cache.getOrCompute('count', () =>
+      RRuleError.wrap(() => this.toRust().count()),
+    );
+  }
+
+  /**
+   * Returns whether the set is finite and, if so, its number of occurrences
+   * and the last one.
+   *
+   * @returns The extent of the set
+   *
+   * @example
+   * ```typescript
+   * const rruleSet = new RRuleSet({
+   *   dtstart: new DtStart(DateTime.date(2024, 1, 1)),
+   *   rrules: [new RRule({ frequency: Frequency.Daily, count: 40 })]
+   * });
+   *
+   * const extent = rruleSet.extent();
+   *
+   * if (extent.finite) {
+   *   console.log(extent.count, extent.last?.toString()); // 40 20240209
+   * }
+   * ```
+   */
+  public extent(): RRuleSetExtent<DT> {
+    return this._cache.getOrCompute<RRuleSetExtent<DT>>('extent', () => {
+      const extent = RRuleError.wrap(() => this.toRust().extent());
+
+      if (!extent.finite) {
+        return { finite: false as const };
+      }
+
+      const count = () => this.count();
+
+      return {
+        finite: true as const,
+        get count() {
+          return count();
+        },
+        last: extent.last ? DateTime.fromInt32Array<DT>(extent.last) : null,
+      };
+    });
+  }
+
+  /**
+   * Returns the number of occurrences between two dates.
+   *
+   * @param after - The lower bound date (exclusive by default)
+   * @param before - The upper bound date (exclusive by default)
+   * @param inclusive - Whether to count the boundary dates
+   * @returns The number of occurrences in the range
+   *
+   * @example
+   * ```typescript
+   * const rruleSet = new RRuleSet({
+   *   dtstart: new DtStart(DateTime.date(2024, 1, 1)),
+   *   rrules: [new RRule(Frequency.Daily)]
+   * });
+   *
+   * rruleSet.countBetween(DateTime.date(2024, 1, 1), DateTime.date(2024, 1, 31), true); // 31
+   * ```
+   */
+  public countBetween(after: DT, before: DT, inclusive?: boolean): number {
+    return this._cache.getOrCompute(
+      `countBetween:${after.toString()},${before.toString()},${inclusive}`,
+      () =>
+        RRuleError.wrap(() =>
+          this.toRust().countBetween(
             after.toInt32Array(),
             before.toInt32Array(),
             inclusive,
@@ -530,6 +993,87 @@ export class RRuleSet<
     );
   }
 
+  /**
+   * Returns the occurrence at the given zero-based index.
+   *
+   * @param index - The index of the occurrence
+   * @returns The occurrence, or `null` if the set has fewer occurrences
+   *
+   * @example
+   * ```typescript
+   * const rruleSet = new RRuleSet({
+   *   dtstart: new DtStart(DateTime.date(2024, 1, 1)),
+   *   rrules: [new RRule(Frequency.Daily)]
+   * });
+   *
+   * rruleSet.nth(11); // 2024-01-12
+   * ```
+   */
+  public nth(index: number): DT | null {
+    return this._cache.getOrCompute(`nth:${index}`, () => {
+      const result = RRuleError.wrap(() => this.toRust().nth(index));
+
+      return result ? DateTime.fromInt32Array<DT>(result) : null;
+    });
+  }
+
+  /**
+   * Returns the zero-based index of the given date among the occurrences.
+   *
+   * @param datetime - The date to look up
+   * @returns The index of the occurrence, or `-1` if the date is not an occurrence
+   *
+   * @example
+   * ```typescript
+   * const rruleSet = new RRuleSet({
+   *   dtstart: new DtStart(DateTime.date(2024, 1, 1)),
+   *   rrules: [new RRule({ frequency: Frequency.Daily, count: 40 })]
+   * });
+   *
+   * // Occurrence 12 of 40
+   * const position = rruleSet.indexOf(DateTime.date(2024, 1, 12)) + 1;
+   * const total = rruleSet.count();
+   * ```
+   */
+  public indexOf(datetime: DT): number {
+    return this._cache.getOrCompute(
+      `indexOf:${datetime.toString()}`,
+      () =>
+        RRuleError.wrap(() => this.toRust().indexOf(datetime.toInt32Array())) ??
+        -1,
+    );
+  }
+
+  /**
+   * Returns the last occurrence before the given date.
+   *
+   * @param datetime - The date to search before
+   * @param inclusive - Whether an occurrence equal to `datetime` is returned
+   * @returns The previous occurrence, or `null` if there is none
+   *
+   * @example
+   * ```typescript
+   * const rruleSet = new RRuleSet({
+   *   dtstart: new DtStart(DateTime.date(2015, 1, 1)),
+   *   rrules: [new RRule(Frequency.Daily)]
+   * });
+   *
+   * const previous = rruleSet.before(DateTime.date(2024, 6, 1)); // 2024-05-31
+   * ```
+   */
+  public before(datetime: DT, inclusive?: boolean): DT | null {
+    return this._cache.getOrCompute(
+      `before:${datetime.toString()},${inclusive}`,
+      () => {
+        const result = RRuleError.wrap(() =>
+          this.toRust().before(datetime.toInt32Array(), inclusive),
+        );
+
+        return result ? DateTime.fromInt32Array<DT>(result) : null;
+      },
+    );
+  }
+
   /**
    * Parses an RFC 5545 string and updates the RRuleSet.
    *
@@ -546,21 +1090,92 @@ export class RRuleSet<
   public setFromString<NDT extends DateTime<Time> | DateTime<undefined> = DT>(
     str: string,
   ): RRuleSet<NDT> {
-    return RRuleSet.fromRust<NDT>(this.toRust().setFromString(str));
+    return RRuleSet.fromRust<NDT>(
+      RRuleError.wrap(() => this.toRust().setFromString(str)),
+    );
+  }
+
+  /**
+   * Binds a floating set, whose DTSTART has neither TZID nor UTC time, to a
+   * time zone. Floating DTSTART, EXDATE and RDATE values keep their
+   * wall-clock time in the time zone, floating UNTIL values are converted
+   * into UTC. Sets which aren't floating are returned unchanged.
+   *
+   * Occurrences of floating sets carry no offset, so they can't be converted
+   * into timestamps until the set is resolved.
+   *
+   * @param tzid - Timezone identifier (e.g., "America/New_York")
+   * @returns A new RRuleSet instance bound to the time zone
+   *
+   * @example
+   * ```typescript
+   * const rruleSet = RRuleSet.fromString(
+   *   'DTSTART:20240115T090000\nRRULE:FREQ=DAILY;COUNT=3',
+   * );
+   * const resolved = rruleSet.resolveFloating('Europe/Paris');
+   * console.log(resolved.toString());
+   * // DTSTART;TZID=Europe/Paris:20240115T090000
+   * // RRULE:FREQ=DAILY;COUNT=3
+   * ```
+   */
+  public resolveFloating(tzid: string): RRuleSet<DT> {
+    return RRuleSet.fromRust<DT>(
+      RRuleError.wrap(() => this.toRust().resolveFloating(tzid)),
+    );
+  }
+
+  /**
+   * Moves the set into another time zone, preserving either the instants or
+   * the wall-clock times of its occurrences. DTSTART, EXDATE and RDATE values
+   * are rewritten as wall-clock times of `tzid`, UNTIL values in UTC.
+   *
+   * The rules are iterated in the wall-clock time of `tzid` afterwards, so
+   * kept instants only hold up to DST transitions the time zones differ in.
+   * Sets of dates are returned as is, floating sets can only keep their
+   * wall-clock times.
+   *
+   * @param tzid - IANA or Windows time zone to move the set into
+   * @param preserve - Whether the instants or the wall-clock times are kept
+   * @returns A new RRuleSet instance in the time zone
+   *
+   * @example
+   * ```typescript
+   * const rruleSet = RRuleSet.fromString(
+   *   'DTSTART;TZID=Europe/Paris:20240115T090000\nRRULE:FREQ=DAILY;COUNT=5',
+   * );
+   *
+   * // DTSTART;TZID=America/New_York:20240115T090000
+   * const relocated = rruleSet.withTimezone('America/New_York', 'wall-clock');
+   *
+   * // DTSTART;TZID=America/New_York:20240115T030000
+   * const shared = rruleSet.withTimezone('America/New_York', 'instant');
+   * ```
+   */
+  public withTimezone(tzid: string, preserve: Preserve): RRuleSet<DT> {
+    return RRuleSet.fromRust<DT>(
+      RRuleError.wrap(() => this.toRust().withTimezone(tzid, preserve)),
+    );
   }
 
   /**
    * @internal
    */
   public toRust(): Rust {
-    this.rust ??= new Rust(
-      this.dtstart.value.toInt32Array(),
-      this.dtstart.tzid,
-      undefined,
-      this.rrules.map((rrule) => rrule.toRust()),
-      this.exrules.map((rrule) => rrule.toRust()),
-      this.exdates.map((exdate) => exdate.toRust()),
-      this.rdates.map((rdate) => rdate.toRust()),
+    this.rust ??= RRuleError.wrap(
+      () =>
+        new Rust(
+          this.dtstart.value.toInt32Array(),
+          this.dtstart.tzid,
+          undefined,
+          this.rrules.map((rrule) => rrule.toRust()),
+          this.exrules.map((rrule) => rrule.toRust()),
+          this.exdates.map((exdate) => exdate.toRust(this.timezones)),
+          this.rdates.map((rdate) => rdate.toRust(this.timezones)),
+          this.dtstart.unknownParameters,
+          this.unknownProperties,
+          this.timezones,
+          this.disambiguation,
+        ),
     );
 
     return this.rust;
@@ -569,6 +1184,7 @@ export class RRuleSet<
   /**
    * Converts the RRuleSet to an RFC 5545 formatted string.
    *
+   * @param fold - Whether to fold the lines longer than 75 octets
    * @returns RFC 5545 formatted string representation
    *
    * @example
@@ -582,8 +1198,41 @@ export class RRuleSet<
    * // RRULE:FREQ=WEEKLY;BYDAY=MO
    * ```
    */
-  public toString(): string {
-    return this.toRust().toString();
+  public toString(fold?: boolean): string {
+    return this.toRust().toString(fold);
+  }
+
+  /**
+   * Converts the RRuleSet to a complete iCalendar document, such as the
+   * content of an .ics file, holding a single VEVENT. Unlike `toString`, the
+   * result can be imported by calendar clients: every TZID comes with a
+   * VTIMEZONE component, and values at a fixed offset are written in UTC.
+   *
+   * @param options - Properties of the VEVENT
+   * @returns iCalendar document with folded, CRLF terminated lines
+   *
+   * @example
+   * ```typescript
+   * const rruleSet = RRuleSet.fromString(
+   *   'DTSTART:20240115T090000Z\nRRULE:FREQ=DAILY;COUNT=5',
+   * );
+   * fs.writeFileSync(
+   *   'standup.ics',
+   *   rruleSet.toCalendar({ summary: 'Standup', duration: 'PT15M' }),
+   * );
+   * ```
+   */
+  public toCalendar(options?: RRuleSetCalendarOptions<DT>): string {
+    return RRuleError.wrap(() =>
+      this.toRust().toCalendar(
+        options?.uid,
+        options?.summary,
+        options?.dtend?.toInt32Array(),
+        options?.duration,
+        options?.dtstamp?.toInt32Array(),
+        options?.components,
+      ),
+    );
   }
 
   /**
@@ -612,6 +1261,9 @@ export class RRuleSet<
       exrules: this.exrules.map((rrule) => rrule.toPlain()),
       exdates: this.exdates.map((rrule) => rrule.toPlain()),
       rdates: this.rdates.map((rrule) => rrule.toPlain()),
+      unknownProperties: this.unknownProperties,
+      timezones: this.timezones,
+      disambiguation: this.disambiguation,
     };
   }
 
@@ -649,7 +1301,7 @@ export class RRuleSet<
 
     const getIterAndStore = () => {
       return (iterAndStore ??= [
-        this.toRust().iterator(cache.values.length),
+        RRuleError.wrap(() => this.toRust().iterator(cache.values.length)),
         new Int32Array(7),
       ]);
     };
@@ -693,6 +1345,162 @@ export class RRuleSet<
     };
   }
 
+  /**
+   * Returns an iterator over the occurrences converted into the given time
+   * zone, keeping their instants. Results are not cached.
+   *
+   * @param tzid - IANA or Windows time zone to convert the occurrences into
+   * @returns An iterator over the occurrences
+   *
+   * @example
+   * ```typescript
+   * for (const occurrence of rruleSet.iterateInTimezone('UTC')) {
+   *   console.log(occurrence.toString()); // 20240115T080000Z, ...
+   * }
+   * ```
+   */
+  public iterateInTimezone(tzid: string): IterableIterator<DT> {
+    return this.iterate(
+      RRuleError.wrap(() => this.toRust().iteratorInTimezone(tzid)),
+    );
+  }
+
+  /**
+   * Returns an iterator over the occurrences as UTC epoch milliseconds.
+   * Timestamps are fetched in batches of `batchSize`. Results are not cached.
+   *
+   * @param batchSize - Number of timestamps fetched at once
+   * @returns An iterator over the timestamps of the occurrences
+   *
+   * @example
+   * ```typescript
+   * for (const timestamp of rruleSet.iterateTimestamps()) {
+   *   console.log(new Date(timestamp).toISOString());
+   * }
+   * ```
+   */
+  public iterateTimestamps(batchSize = 256): IterableIterator<number> {
+    const iter: RRuleSetTimestampIterator = RRuleError.wrap(() =>
+      this.toRust().timestampIterator(),
+    );
+    let batch = new Float64Array(0);
+    let index = 0;
+
+    return {
+      next: () => {
+        if (index === batch.length) {
+          batch = iter.next(batchSize);
+          index = 0;
+        }
+
+        if (index === batch.length) {
+          return {
+            done: true as const,
+            value: undefined,
+          };
+        }
+
+        return {
+          done: false,
+          value: batch[index++]!,
+        };
+      },
+      [Symbol.iterator]() {
+        return this;
+      },
+    };
+  }
+
+  /**
+   * Returns an iterator over the occurrences starting at the given date.
+   *
+   * The iteration does not enumerate the occurrences preceding `datetime`, so
+   * resuming a long-running series is as fast as starting a new one.
+   * Results are not cached.
+   *
+   * @param datetime - The date to start iterating at
+   * @param inclusive - Whether an occurrence equal to `datetime` is yielded
+   * @returns An iterator over the occurrences
+   *
+   * @example
+   * ```typescript
+   * const rruleSet = new RRuleSet({
+   *   dtstart: new DtStart(DateTime.date(2015, 1, 1)),
+   *   rrules: [new RRule(Frequency.Daily)]
+   * });
+   *
+   * for (const occurrence of rruleSet.iterateFrom(DateTime.date(2024, 6, 1), true)) {
+   *   console.log(occurrence.toString()); // 20240601, 20240602, ...
+   * }
+   * ```
+   */
+  public iterateFrom(datetime: DT, inclusive?: boolean): IterableIterator<DT> {
+    return this.iterate(
+      RRuleError.wrap(() =>
+        this.toRust().iteratorFrom(datetime.toInt32Array(), inclusive),
+      ),
+    );
+  }
+
+  /**
+   * Returns an iterator over the occurrences preceding the given date, in
+   * descending order down to the start of the set.
+   *
+   * Useful for paging backwards through a series, e.g. from today.
+   * Results are not cached.
+   *
+   * @param datetime - The date to start iterating backwards at
+   * @param inclusive - Whether an occurrence equal to `datetime` is yielded
+   * @returns An iterator over the occurrences in descending order
+   *
+   * @example
+   * ```typescript
+   * const rruleSet = new RRuleSet({
+   *   dtstart: new DtStart(DateTime.date(2015, 1, 1)),
+   *   rrules: [new RRule(Frequency.Daily)]
+   * });
+   *
+   * for (const occurrence of rruleSet.iterateBackwardsFrom(DateTime.date(2024, 6, 1))) {
+   *   console.log(occurrence.toString()); // 20240531, 20240530, ...
+   * }
+   * ```
+   */
+  public iterateBackwardsFrom(
+    datetime: DT,
+    inclusive?: boolean,
+  ): IterableIterator<DT> {
+    return this.iterate(
+      RRuleError.wrap(() =>
+        this.toRust().reverseIteratorFrom(datetime.toInt32Array(), inclusive),
+      ),
+    );
+  }
+
+  private iterate(iter: RRuleSetIterator): IterableIterator<DT> {
+    const store = new Int32Array(7);
+
+    return {
+      next: () => {
+        const next = RRuleError.wrap(() => iter.next(store));
+
+        if (!next) {
+          return {
+            done: true as const,
+            value: undefined,
+          };
+        }
+
+        return {
+          done: false,
+          value: DateTime.fromInt32Array<DT>(next === true ? store : next),
+        };
+      },
+      [Symbol.iterator]() {
+        return this;
+      },
+    };
+  }
+
   private toOptions(): RRuleSetOptions<DT, RRDT, ERDT> {
     return {
       dtstart: this.dtstart,
@@ -700,6 +1508,9 @@ export class RRuleSet<
       exrules: this.exrules,
       exdates: this.exdates,
       rdates: this.rdates,
+      unknownProperties: this.unknownProperties,
+      timezones: this.timezones,
+      disambiguation: this.disambiguation,
     };
   }
 }
@@ -4,7 +4,10 @@ import {
   type DateTimeLike,
   type DateLike,
 } from './datetime';
+import { type DtStart } from './dtstart';
+import { RRuleError } from './error';
 import { RRule as Rust } from './lib';
+import { nonEmpty, type UnknownParameter } from './unknown';
 
 /**
  * Represents a weekday with an optional occurrence number.
@@ -33,6 +36,30 @@ export interface NWeekday {
   weekday: Weekday;
 }
 
+/**
+ * Describes a problem found in a recurrence rule.
+ *
+ * @example
+ * ```typescript
+ * const diagnostic: RRuleDiagnostic = {
+ *   severity: 'error',
+ *   code: 'NO_MATCHING_DATE',
+ *   message: 'No date satisfies BYMONTH=2;BYMONTHDAY=30',
+ * };
+ * ```
+ */
+export interface RRuleDiagnostic {
+  /**
+   * `error` if the rule can never produce an occurrence, `warning` if it
+   * misuses RFC 5545 but may still produce occurrences
+   */
+  readonly severity: 'error' | 'warning';
+  /** Stable identifier of the problem, e.g. `NO_MATCHING_DATE` */
+  readonly code: string;
+  /** Human readable description of the problem */
+  readonly message: string;
+}
+
 /**
  * Recurrence frequency enumeration.
  */
@@ -111,6 +138,10 @@ export interface RRuleOptions<
   readonly byYearday?: readonly number[];
   /** Which day the week starts on */
   readonly weekstart?: Weekday;
+  /** Parameters of the RRULE property, preserved as parsed */
+  readonly unknownParameters?: readonly UnknownParameter[];
+  /** Rule parts which are not interpreted (e.g., X-NAME), preserved as parsed */
+  readonly unknownParts?: readonly UnknownParameter[];
 }
 
 /**
@@ -145,6 +176,10 @@ export interface RRuleLike<DT extends DateTimeLike | DateLike | undefined> {
   readonly byYearday: readonly number[];
   /** Which day the week starts on */
   readonly weekstart?: Weekday;
+  /** Parameters of the RRULE property, preserved as parsed */
+  readonly unknownParameters?: readonly UnknownParameter[];
+  /** Rule parts which are not interpreted (e.g., X-NAME), preserved as parsed */
+  readonly unknownParts?: readonly UnknownParameter[];
 }
 
 /**
@@ -211,6 +246,10 @@ export class RRule<
   public readonly byYearday: readonly number[];
   /** Which day the week starts on */
   public readonly weekstart?: Weekday;
+  /** Parameters of the RRULE property, preserved as parsed */
+  public readonly unknownParameters?: readonly UnknownParameter[];
+  /** Rule parts which are not interpreted (e.g., X-NAME), preserved as parsed */
+  public readonly unknownParts?: readonly UnknownParameter[];
 
   /** @internal */
   private rust?: Rust;
@@ -233,6 +272,8 @@ export class RRule<
       this.byWeekno = frequencyOrOptions.byWeekno ?? [];
       this.byYearday = frequencyOrOptions.byYearday ?? [];
       this.weekstart = frequencyOrOptions.weekstart;
+      this.unknownParameters = frequencyOrOptions.unknownParameters;
+      this.unknownParts = frequencyOrOptions.unknownParts;
     } else {
       this.frequency = frequencyOrOptions;
       this.until = undefined as DT;
@@ -262,7 +303,7 @@ export class RRule<
   public static fromString<DT extends DateTime<Time> | DateTime<undefined>>(
     str: string,
   ): RRule<DT> {
-    const rust = Rust.parse(str);
+    const rust = RRuleError.wrap(() => Rust.parse(str));
 
     return this.fromRust(rust);
   }
@@ -316,6 +357,8 @@ export class RRule<
       byWeekno: rrule.byWeekno,
       byYearday: rrule.byYearday,
       weekstart: rrule.weekstart,
+      unknownParameters: rrule.unknownParameters,
+      unknownParts: rrule.unknownParts,
     });
   }
 
@@ -342,6 +385,8 @@ export class RRule<
       byWeekno: rust.byWeekno,
       byYearday: rust.byYearday,
       weekstart: rust.weekstart ?? undefined,
+      unknownParameters: nonEmpty(rust.unknownParameters),
+      unknownParts: nonEmpty(rust.unknownParts),
     });
 
     rrule.rust = rust;
@@ -586,6 +631,49 @@ export class RRule<
     return new RRule({ ...this.toOptions(), until });
   }
 
+  /**
+   * Detects combinations of rule parts that can never produce an occurrence.
+   *
+   * Only the parts set on the rule are analyzed, defaults derived from DTSTART
+   * are not.
+   *
+   * @returns The problems found, empty if none
+   *
+   * @example
+   * ```typescript
+   * const rrule = new RRule({
+   *   frequency: Frequency.Yearly,
+   *   byMonth: [Month.February],
+   *   byMonthday: [30]
+   * });
+   * rrule.validate(); // [{ severity: 'error', code: 'NO_MATCHING_DATE', ... }]
+   * ```
+   */
+  public validate(): RRuleDiagnostic[] {
+    return this.toRust().validate();
+  }
+
+  /**
+   * Reports constructions RFC 5545 forbids or defines as unusual, such as
+   * BYWEEKNO outside of a yearly rule or COUNT and UNTIL used together.
+   *
+   * @param dtstart - The start of the set, enables checks depending on it
+   * @returns The warnings found, empty if none
+   *
+   * @example
+   * ```typescript
+   * const rrule = RRule.fromString('FREQ=DAILY;COUNT=10;UNTIL=20240101T000000Z');
+   * rrule.lint(); // [{ severity: 'warning', code: 'COUNT_AND_UNTIL', ... }]
+   * ```
+   */
+  public lint(
+    dtstart?: DtStart<DateTime<Time> | DateTime<undefined>>,
+  ): RRuleDiagnostic[] {
+    return RRuleError.wrap(() =>
+      this.toRust().lint(dtstart?.value.toInt32Array(), dtstart?.tzid),
+    );
+  }
+
   /**
    * Converts the RRule to an RFC 5545 RRULE string.
    *
@@ -609,21 +697,26 @@ export class RRule<
    * @internal
    */
   public toRust(): Rust {
-    this.rust ??= new Rust(
-      this.frequency,
-      this.interval,
-      this.count,
-      this.weekstart,
-      this.until?.toInt32Array(),
-      this.byWeekday,
-      this.byHour,
-      this.byMinute,
-      this.bySecond,
-      this.byMonthday,
-      this.bySetpos,
-      this.byMonth,
-      this.byWeekno,
-      this.byYearday,
+    this.rust ??= RRuleError.wrap(
+      () =>
+        new Rust(
+          this.frequency,
+          this.interval,
+          this.count,
+          this.weekstart,
+          this.until?.toInt32Array(),
+          this.byWeekday,
+          this.byHour,
+          this.byMinute,
+          this.bySecond,
+          this.byMonthday,
+          this.bySetpos,
+          this.byMonth,
+          this.byWeekno,
+          this.byYearday,
+          this.unknownParameters,
+          this.unknownParts,
+        ),
     );
 
     return this.rust;
@@ -669,6 +762,8 @@ export class RRule<
       byYearday: this.byYearday,
       weekstart: this.weekstart,
       until: this.until?.toPlain(),
+      unknownParameters: this.unknownParameters,
+      unknownParts: this.unknownParts,
     };
   }
 
@@ -688,6 +783,8 @@ export class RRule<
       byYearday: this.byYearday,
       weekstart: this.weekstart,
       until: this.until,
+      unknownParameters: this.unknownParameters,
+      unknownParts: this.unknownParts,
     };
   }
 }
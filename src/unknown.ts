@@ -0,0 +1,40 @@
+/**
+ * Parameter, or RRULE part, which is not interpreted but preserved when
+ * converting back to a string.
+ *
+ * @example
+ * ```typescript
+ * // DTSTART;X-SOURCE="https://example.com":20240115T090000Z
+ * const parameter: UnknownParameter = {
+ *   name: 'X-SOURCE',
+ *   values: ['https://example.com'],
+ * };
+ * ```
+ */
+export interface UnknownParameter {
+  /** Uppercased name of the parameter */
+  readonly name: string;
+  /** Unquoted and decoded values of the parameter */
+  readonly values: readonly string[];
+}
+
+/**
+ * Property which is not interpreted but preserved when converting back to a
+ * string, e.g. `X-WR-CALNAME` or `SUMMARY`.
+ */
+export interface UnknownProperty {
+  /** Uppercased name of the property */
+  readonly name: string;
+  /** Parameters of the property, in order */
+  readonly parameters: readonly UnknownParameter[];
+  /** Raw value of the property */
+  readonly value: string;
+}
+
+/**
+ * Returns `values`, or `undefined` if there are none.
+ * @internal
+ */
+export function nonEmpty<T>(values: readonly T[]): readonly T[] | undefined {
+  return values.length > 0 ? values : undefined;
+}
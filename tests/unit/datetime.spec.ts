@@ -102,6 +102,26 @@ describe(DateTime, () => {
     });
   });
 
+  describe('offset', () => {
+    it('should create object at fixed offset', () => {
+      const datetime = DateTime.offset(2024, 1, 15, 14, 30, 0, 19800);
+
+      expect(datetime.time.utc).toBeFalsy();
+      expect(datetime.toTimestamp()).toBe(Date.UTC(2024, 0, 15, 9, 0, 0));
+      expect(datetime.toString()).toBe('20240115T143000+0530');
+    });
+
+    it.each([
+      { offset: 0, expected: '20240115T143000Z' },
+      { offset: -3600 * 8, expected: '20240115T143000-0800' },
+      { offset: -90, expected: '20240115T143000-000130' },
+    ])('should convert offset $offset to string', ({ offset, expected }) => {
+      expect(DateTime.offset(2024, 1, 15, 14, 30, 0, offset).toString()).toBe(
+        expected,
+      );
+    });
+  });
+
   describe('local', () => {
     it('should create local object from', () => {
       const datetime = DateTime.local(2005, 9, 4, 9, 1, 2);
@@ -478,6 +498,18 @@ describe(DateTime, () => {
           utc: true,
         },
       },
+      {
+        input: '20240115T143000+0530',
+        expected: {
+          year: 2024,
+          month: 1,
+          day: 15,
+          hour: 14,
+          minute: 30,
+          second: 0,
+          utc: false,
+        },
+      },
     ])('should create datetime from $input', ({ input, expected }) => {
       const datetime = DateTime.fromString(input);
 
@@ -500,5 +532,23 @@ describe(DateTime, () => {
 
       expect(act).toThrow('Invalid date');
     });
+
+    it.each([
+      { input: '20240115T143000+0530', expected: 19800 },
+      { input: '20240115T143000-093015', expected: -34215 },
+    ])('should parse offset of $input', ({ input, expected }) => {
+      const datetime = DateTime.fromString(input);
+
+      expect(datetime.toTimestamp()).toBe(
+        Date.UTC(2024, 0, 15, 14, 30, 0) - expected * 1000,
+      );
+      expect(datetime.toString()).toBe(input);
+    });
+
+    it('should fail to create datetime with invalid offset', () => {
+      expect(() => DateTime.fromString('20240115T143000*0530')).toThrow(
+        'Invalid offset',
+      );
+    });
   });
 });
@@ -0,0 +1,216 @@
+import {
+  DateTime,
+  DtStart,
+  ExDate,
+  Frequency,
+  RDate,
+  RRule,
+  RRuleError,
+  RRuleSet,
+  type Time,
+} from '../../src';
+import { describe, it, expect } from 'vitest';
+
+const occurrences = (set: RRuleSet<DateTime<Time> | DateTime<undefined>>) =>
+  set.all().map((datetime) => datetime.toString());
+
+describe('Disambiguation', () => {
+  const gap = 'DTSTART;TZID=Europe/Berlin:20240331T023000';
+  const overlap = 'DTSTART;TZID=Europe/Berlin:20241027T023000';
+
+  it('should reject times in a gap by default', () => {
+    const set = RRuleSet.fromString(`${gap}\nRRULE:FREQ=DAILY;COUNT=2`);
+    const act = () => set.all();
+
+    expect(set.disambiguation).toBeUndefined();
+    expect(act).toThrow(RRuleError);
+    expect(act).toThrow(
+      'Local time 20240331T023000 does not exist in Europe/Berlin',
+    );
+  });
+
+  it('should reject times in an overlap by default', () => {
+    const set = RRuleSet.fromString(`${overlap}\nRRULE:FREQ=DAILY;COUNT=2`);
+
+    expect(() => set.all()).toThrow(
+      'Local time 20241027T023000 is ambiguous in Europe/Berlin',
+    );
+  });
+
+  it.each([
+    {
+      disambiguation: 'earlier' as const,
+      expected: ['20240331T013000+0100', '20240401T023000+0200'],
+    },
+    {
+      disambiguation: 'later' as const,
+      expected: ['20240331T033000+0200', '20240401T023000+0200'],
+    },
+    {
+      disambiguation: 'shift-forward' as const,
+      expected: ['20240331T033000+0200', '20240401T023000+0200'],
+    },
+  ])(
+    'should resolve DTSTART in a gap ($disambiguation)',
+    ({ disambiguation, expected }) => {
+      const set = RRuleSet.fromString(
+        `${gap}\nRRULE:FREQ=DAILY;COUNT=2`,
+      ).setDisambiguation(disambiguation);
+
+      expect(occurrences(set)).toEqual(expected);
+    },
+  );
+
+  it.each([
+    {
+      disambiguation: 'earlier' as const,
+      expected: Date.UTC(2024, 9, 27, 0, 30),
+    },
+    {
+      disambiguation: 'later' as const,
+      expected: Date.UTC(2024, 9, 27, 1, 30),
+    },
+    {
+      disambiguation: 'shift-forward' as const,
+      expected: Date.UTC(2024, 9, 27, 0, 30),
+    },
+  ])(
+    'should resolve DTSTART in an overlap ($disambiguation)',
+    ({ disambiguation, expected }) => {
+      const set = RRuleSet.fromString(
+        `${overlap}\nRRULE:FREQ=DAILY;COUNT=2`,
+      ).setDisambiguation(disambiguation);
+
+      expect(set.all().map((datetime) => datetime.toTimestamp())).toEqual([
+        expected,
+        Date.UTC(2024, 9, 28, 1, 30),
+      ]);
+    },
+  );
+
+  it.each([
+    {
+      disambiguation: 'reject' as const,
+      expected: [
+        '20240330T023000+0100',
+        '20240331T033000+0200',
+        '20240401T023000+0200',
+      ],
+    },
+    {
+      disambiguation: 'earlier' as const,
+      expected: [
+        '20240330T023000+0100',
+        '20240331T013000+0100',
+        '20240401T023000+0200',
+      ],
+    },
+    {
+      disambiguation: 'later' as const,
+      expected: [
+        '20240330T023000+0100',
+        '20240331T033000+0200',
+        '20240401T023000+0200',
+      ],
+    },
+  ])(
+    'should resolve generated occurrences in a gap ($disambiguation)',
+    ({ disambiguation, expected }) => {
+      const set = RRuleSet.fromString(
+        'DTSTART;TZID=Europe/Berlin:20240330T023000\nRRULE:FREQ=DAILY;COUNT=3',
+      ).setDisambiguation(disambiguation);
+
+      expect(occurrences(set)).toEqual(expected);
+    },
+  );
+
+  it('should keep occurrences ordered when shifting them forward', () => {
+    const set = RRuleSet.fromString(
+      [
+        'DTSTART;TZID=Europe/Berlin:20240331T010000',
+        'RRULE:FREQ=MINUTELY;INTERVAL=25;COUNT=8',
+      ].join('\n'),
+    ).setDisambiguation('shift-forward');
+
+    expect(occurrences(set)).toEqual([
+      '20240331T010000+0100',
+      '20240331T012500+0100',
+      '20240331T015000+0100',
+      '20240331T030500+0200',
+      '20240331T031500+0200',
+      '20240331T033000+0200',
+      '20240331T034000+0200',
+      '20240331T035500+0200',
+    ]);
+  });
+
+  it('should count occurrences shifted onto other ones', () => {
+    const set = RRuleSet.fromString(
+      [
+        'DTSTART;TZID=Europe/Berlin:20240331T010000',
+        'RRULE:FREQ=MINUTELY;INTERVAL=30;COUNT=6',
+      ].join('\n'),
+    ).setDisambiguation('later');
+
+    expect(occurrences(set)).toEqual([
+      '20240331T010000+0100',
+      '20240331T013000+0100',
+      '20240331T030000+0200',
+      '20240331T030000+0200',
+      '20240331T033000+0200',
+      '20240331T033000+0200',
+    ]);
+  });
+
+  it('should shift generated occurrences in a gap forward by default', () => {
+    const set = RRuleSet.fromString(
+      'DTSTART;TZID=America/New_York:20240309T023000\nRRULE:FREQ=DAILY;COUNT=3',
+    );
+
+    expect(occurrences(set)).toEqual([
+      '20240309T023000-0500',
+      '20240310T033000-0400',
+      '20240311T023000-0400',
+    ]);
+  });
+
+  it('should apply the policy to EXDATE, RDATE and UNTIL', () => {
+    const set = new RRuleSet({
+      dtstart: new DtStart(
+        DateTime.create(2024, 3, 29, 2, 30, 0, false),
+        'Europe/Berlin',
+      ),
+      rrules: [
+        new RRule(Frequency.Daily).setUntil(
+          DateTime.create(2024, 3, 31, 2, 30, 0, false),
+        ),
+      ],
+      exdates: [
+        new ExDate([DateTime.create(2024, 3, 31, 2, 30, 0, false)]),
+      ],
+      rdates: [new RDate([DateTime.create(2024, 10, 27, 2, 30, 0, false)])],
+      disambiguation: 'later',
+    });
+
+    expect(set.all().map((datetime) => datetime.toTimestamp())).toEqual([
+      Date.UTC(2024, 2, 29, 1, 30),
+      Date.UTC(2024, 2, 30, 1, 30),
+      Date.UTC(2024, 9, 27, 1, 30),
+    ]);
+    expect(() => set.setDisambiguation('reject').all()).toThrow(RRuleError);
+  });
+
+  it('should be preserved across updates', () => {
+    const set = RRuleSet.fromString(`${gap}\nRRULE:FREQ=DAILY;COUNT=2`)
+      .setDisambiguation('later')
+      .setRDates([]);
+
+    expect(set.disambiguation).toBe('later');
+    expect(set.toPlain().disambiguation).toBe('later');
+    expect(RRuleSet.fromPlain(set.toPlain()).all()).toEqualPlain(set.all());
+    expect(
+      set.setFromString('EXDATE;TZID=Europe/Berlin:20240401T023000')
+        .disambiguation,
+    ).toBe('later');
+  });
+});
@@ -0,0 +1,86 @@
+import { DateTime, DtStart, RRule, RRuleSet } from '../../src';
+import { describe, it, expect } from 'vitest';
+
+describe('Floating time', () => {
+  const str = [
+    'DTSTART:20240330T023000',
+    'RRULE:FREQ=DAILY;UNTIL=20240402T023000',
+    'EXDATE:20240331T023000',
+    'RDATE:20240410T120000',
+  ].join('\n');
+
+  it('should iterate in wall-clock time', () => {
+    const set = RRuleSet.fromString(str);
+
+    expect(set.dtstart.tzid).toBeUndefined();
+    expect(set.all()).toEqualPlain([
+      DateTime.create(2024, 3, 30, 2, 30, 0, false),
+      DateTime.create(2024, 4, 1, 2, 30, 0, false),
+      DateTime.create(2024, 4, 2, 2, 30, 0, false),
+      DateTime.create(2024, 4, 10, 12, 0, 0, false),
+    ]);
+  });
+
+  it('should be serialized without TZID', () => {
+    expect(RRuleSet.fromString(str).toString()).toBe(str);
+  });
+
+  it('should accept floating DTSTART in constructor', () => {
+    const set = new RRuleSet({
+      dtstart: new DtStart(DateTime.create(2024, 1, 1, 9, 0, 0, false)),
+      rrules: [RRule.fromString('FREQ=DAILY;COUNT=2')],
+    });
+
+    expect(set.toString()).toBe(
+      'DTSTART:20240101T090000\nRRULE:FREQ=DAILY;COUNT=2',
+    );
+    expect(
+      set.between(
+        DateTime.create(2024, 1, 2, 0, 0, 0, false),
+        DateTime.create(2024, 1, 3, 0, 0, 0, false),
+      ),
+    ).toEqualPlain([DateTime.create(2024, 1, 2, 9, 0, 0, false)]);
+  });
+
+  it('should produce occurrences without offset', () => {
+    const [occurrence] = RRuleSet.fromString(str).all(1);
+
+    expect(() => occurrence?.toTimestamp()).toThrow(
+      'There is no information about time zone offset',
+    );
+  });
+
+  it('should resolve floating set into time zone', () => {
+    const set = RRuleSet.fromString(
+      [
+        'DTSTART:20240330T123000',
+        'RRULE:FREQ=DAILY;UNTIL=20240402T123000',
+        'EXDATE:20240331T123000',
+        'RDATE:20240410T120000',
+      ].join('\n'),
+    ).resolveFloating('Europe/Berlin');
+
+    expect(set.toString()).toBe(
+      [
+        'DTSTART;TZID=Europe/Berlin:20240330T123000',
+        'RRULE:FREQ=DAILY;UNTIL=20240402T103000Z',
+        'EXDATE;TZID=Europe/Berlin:20240331T123000',
+        'RDATE;TZID=Europe/Berlin:20240410T120000',
+      ].join('\n'),
+    );
+    expect(set.all().map((occurrence) => occurrence.toTimestamp())).toEqual([
+      Date.UTC(2024, 2, 30, 11, 30),
+      Date.UTC(2024, 3, 1, 10, 30),
+      Date.UTC(2024, 3, 2, 10, 30),
+      Date.UTC(2024, 3, 10, 10, 0),
+    ]);
+  });
+
+  it('should leave sets which are not floating unchanged', () => {
+    const str = 'DTSTART;TZID=Asia/Tokyo:20240101T090000\nRRULE:FREQ=DAILY';
+
+    expect(
+      RRuleSet.fromString(str).resolveFloating('Europe/Berlin').toString(),
+    ).toBe(str);
+  });
+});
@@ -0,0 +1,118 @@
+import { DateTime, RRuleError, RRuleSet } from '../../src';
+import { describe, it, expect } from 'vitest';
+
+describe('Time zone projection', () => {
+  const set = RRuleSet.fromString(
+    'DTSTART;TZID=Europe/Paris:20240329T090000\nRRULE:FREQ=DAILY;COUNT=4',
+  );
+
+  it.each([
+    {
+      tzid: 'Asia/Tokyo',
+      expected: [
+        '20240329T170000+0900',
+        '20240330T170000+0900',
+        '20240331T160000+0900',
+        '20240401T160000+0900',
+      ],
+    },
+    {
+      tzid: 'America/New_York',
+      expected: [
+        '20240329T040000-0400',
+        '20240330T040000-0400',
+        '20240331T030000-0400',
+        '20240401T030000-0400',
+      ],
+    },
+    {
+      tzid: 'UTC',
+      expected: [
+        '20240329T080000Z',
+        '20240330T080000Z',
+        '20240331T070000Z',
+        '20240401T070000Z',
+      ],
+    },
+  ])('should convert all occurrences into $tzid', ({ tzid, expected }) => {
+    const occurrences = set.allInTimezone(tzid);
+
+    expect(occurrences.map((datetime) => datetime.toString())).toEqual(
+      expected,
+    );
+    expect(occurrences.map((datetime) => datetime.toTimestamp())).toEqual(
+      set.all().map((datetime) => datetime.toTimestamp()),
+    );
+  });
+
+  it('should limit the converted occurrences', () => {
+    expect(
+      set.allInTimezone('Asia/Tokyo', 2).map((datetime) => datetime.toString()),
+    ).toEqual(['20240329T170000+0900', '20240330T170000+0900']);
+  });
+
+  it('should convert occurrences between bounds given in the set time zone', () => {
+    const occurrences = set.betweenInTimezone(
+      'UTC',
+      DateTime.local(2024, 3, 30, 9, 0, 0),
+      DateTime.local(2024, 4, 1, 9, 0, 0),
+      true,
+    );
+
+    expect(occurrences.map((datetime) => datetime.toString())).toEqual([
+      '20240330T080000Z',
+      '20240331T070000Z',
+      '20240401T070000Z',
+    ]);
+  });
+
+  it('should iterate the converted occurrences', () => {
+    const [first, second] = set.iterateInTimezone('Asia/Kolkata');
+
+    expect(first?.toString()).toBe('20240329T133000+0530');
+    expect(second?.toString()).toBe('20240330T133000+0530');
+  });
+
+  it('should convert RDATE values of another time zone', () => {
+    const set = RRuleSet.fromString(
+      [
+        'DTSTART:20240329T090000Z',
+        'RDATE;TZID=Asia/Kolkata:20240330T120000',
+        'RRULE:FREQ=DAILY;COUNT=2',
+      ].join('\n'),
+    );
+
+    expect(
+      set.allInTimezone('Asia/Kolkata').map((datetime) => datetime.toString()),
+    ).toEqual([
+      '20240329T143000+0530',
+      '20240330T120000+0530',
+      '20240330T143000+0530',
+    ]);
+  });
+
+  it('should leave dates as is', () => {
+    const set = RRuleSet.fromString(
+      'DTSTART;VALUE=DATE:20240329\nRRULE:FREQ=DAILY;COUNT=2',
+    );
+
+    expect(
+      set.allInTimezone('Asia/Tokyo').map((datetime) => datetime.toString()),
+    ).toEqual(['20240329', '20240330']);
+  });
+
+  it('should not convert floating occurrences', () => {
+    const set = RRuleSet.fromString(
+      'DTSTART:20240329T090000\nRRULE:FREQ=DAILY;COUNT=2',
+    );
+    const act = () => set.allInTimezone('UTC');
+
+    expect(act).toThrow(RRuleError);
+    expect(act).toThrow('TZID is required for non-UTC DTSTART');
+    expect(() => set.iterateInTimezone('UTC')).toThrow(RRuleError);
+  });
+
+  it('should throw on an unknown time zone', () => {
+    expect(() => set.allInTimezone('Mars/Olympus')).toThrow(RRuleError);
+  });
+});
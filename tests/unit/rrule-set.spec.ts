@@ -1,6 +1,7 @@
 import {
   DtStart,
   DateTime,
+  type Time,
   Frequency,
   RRule,
   RRuleSet,
@@ -247,6 +248,248 @@ describe(RRuleSet, () => {
       ]);
       expect(dates1).toBe(dates2);
     });
+
+    it('should return occurrences of a long-running series', () => {
+      const set = new RRuleSet(
+        new DtStart({
+          value: DateTime.local(2015, 1, 1, 0, 0, 0),
+          tzid: 'UTC',
+        }),
+      ).addRRule(new RRule(Frequency.Hourly).setInterval(5));
+
+      expect(
+        set.between(
+          DateTime.local(2024, 6, 1, 0, 0, 0),
+          DateTime.local(2024, 6, 1, 12, 0, 0),
+        ),
+      ).toEqualPlain([
+        DateTime.local(2024, 6, 1, 4, 0, 0),
+        DateTime.local(2024, 6, 1, 9, 0, 0),
+      ]);
+    });
+  });
+
+  describe('after', () => {
+    it('should return next occurrence', () => {
+      const set = new RRuleSet(
+        new DtStart({
+          value: DateTime.local(2015, 1, 1, 9, 0, 0),
+          tzid: 'US/Eastern',
+        }),
+      ).addRRule(new RRule(Frequency.Daily));
+
+      expect(set.after(DateTime.local(2024, 6, 1, 9, 0, 0))).toEqualPlain(
+        DateTime.local(2024, 6, 2, 9, 0, 0),
+      );
+      expect(
+        set.after(DateTime.local(2024, 6, 1, 9, 0, 0), true),
+      ).toEqualPlain(DateTime.local(2024, 6, 1, 9, 0, 0));
+    });
+
+    it('should return null if there is no next occurrence', () => {
+      const set = new RRuleSet(
+        new DtStart({
+          value: DateTime.date(1997, 9, 2),
+          tzid: 'US/Eastern',
+        }),
+      ).addRRule(new RRule(Frequency.Daily).setCount(10));
+
+      expect(set.after(DateTime.date(1997, 9, 11))).toBeNull();
+    });
+  });
+
+  describe('count', () => {
+    it('should return number of occurrences', () => {
+      const set = new RRuleSet(
+        new DtStart({
+          value: DateTime.date(1997, 9, 2),
+          tzid: 'US/Eastern',
+        }),
+      )
+        .addRRule(new RRule(Frequency.Daily).setCount(40))
+        .addExDate(new ExDate([DateTime.date(1997, 9, 4)]));
+
+      expect(set.count()).toBe(39);
+    });
+
+    it('should throw for infinite set', () => {
+      const set = new RRuleSet(
+        new DtStart({
+          value: DateTime.date(1997, 9, 2),
+          tzid: 'US/Eastern',
+        }),
+      ).addRRule(new RRule(Frequency.Daily));
+
+      expect(() => set.count()).toThrow(
+        'Cannot count occurrences of an infinite set',
+      );
+    });
+  });
+
+  describe('extent', () => {
+    it('should return count and last occurrence of finite set', () => {
+      const set = new RRuleSet(
+        new DtStart({
+          value: DateTime.date(1997, 9, 2),
+          tzid: 'US/Eastern',
+        }),
+      )
+        .addRRule(
+          new RRule(Frequency.Weekly).setUntil(DateTime.date(1997, 10, 14)),
+        )
+        .addExDate(new ExDate([DateTime.date(1997, 9, 9)]))
+        .addRDate(new RDate([DateTime.date(1997, 10, 20)]));
+      const extent = set.extent();
+
+      expect(extent).toMatchObject({ finite: true, count: 7 });
+      expect(extent.finite ? extent.last : null).toEqualPlain(
+        DateTime.date(1997, 10, 20),
+      );
+    });
+
+    it('should return infinite for unbounded set', () => {
+      const set = new RRuleSet(
+        new DtStart({
+          value: DateTime.date(1997, 9, 2),
+          tzid: 'US/Eastern',
+        }),
+      )
+        .addRRule(new RRule(Frequency.Daily).setCount(10))
+        .addRRule(new RRule(Frequency.Weekly));
+
+      expect(set.extent()).toEqual({ finite: false });
+    });
+
+    it('should not iterate large sets', () => {
+      const set = RRuleSet.fromString(
+        'DTSTART:20240101T000000Z\nRRULE:FREQ=SECONDLY;COUNT=2000000000',
+      );
+      const extent = set.extent();
+
+      expect(extent).toMatchObject({ finite: true, count: 2000000000 });
+      expect(extent.finite ? extent.last?.toString() : null).toBe(
+        '20870518T033319Z',
+      );
+    });
+
+    it('should look up the last occurrence of sets limited by UNTIL', () => {
+      const set = RRuleSet.fromString(
+        [
+          'DTSTART:20240101T000000Z',
+          'RRULE:FREQ=SECONDLY;UNTIL=20900101T000000Z',
+          'EXDATE:20900101T000000Z',
+        ].join('\n'),
+      );
+      const extent = set.extent();
+
+      expect(extent.finite ? extent.last?.toString() : null).toBe(
+        '20891231T235959Z',
+      );
+    });
+  });
+
+  describe('countBetween', () => {
+    it('should return number of occurrences in range', () => {
+      const set = new RRuleSet(
+        new DtStart({
+          value: DateTime.date(2015, 1, 1),
+          tzid: 'US/Eastern',
+        }),
+      ).addRRule(new RRule(Frequency.Daily));
+
+      expect(
+        set.countBetween(
+          DateTime.date(2024, 1, 1),
+          DateTime.date(2024, 1, 31),
+          true,
+        ),
+      ).toBe(31);
+      expect(
+        set.countBetween(DateTime.date(2024, 1, 1), DateTime.date(2024, 1, 31)),
+      ).toBe(29);
+    });
+  });
+
+  describe('nth', () => {
+    it('should return occurrence at index', () => {
+      const set = new RRuleSet(
+        new DtStart({
+          value: DateTime.date(1997, 9, 2),
+          tzid: 'US/Eastern',
+        }),
+      ).addRRule(new RRule(Frequency.Daily).setCount(10));
+
+      expect(set.nth(0)).toEqualPlain(DateTime.date(1997, 9, 2));
+      expect(set.nth(9)).toEqualPlain(DateTime.date(1997, 9, 11));
+      expect(set.nth(10)).toBeNull();
+    });
+  });
+
+  describe('indexOf', () => {
+    it('should return index of occurrence', () => {
+      const set = new RRuleSet(
+        new DtStart({
+          value: DateTime.date(1997, 9, 2),
+          tzid: 'US/Eastern',
+        }),
+      )
+        .addRRule(new RRule(Frequency.Daily).setCount(10))
+        .addExDate(new ExDate([DateTime.date(1997, 9, 4)]));
+
+      expect(set.indexOf(DateTime.date(1997, 9, 2))).toBe(0);
+      expect(set.indexOf(DateTime.date(1997, 9, 5))).toBe(2);
+      expect(set.indexOf(DateTime.date(1997, 9, 4))).toBe(-1);
+      expect(set.indexOf(DateTime.date(1997, 9, 12))).toBe(-1);
+    });
+  });
+
+  describe('includes', () => {
+    it('should return whether date is an occurrence', () => {
+      const set = new RRuleSet(
+        new DtStart({
+          value: DateTime.local(2015, 1, 1, 9, 0, 0),
+          tzid: 'US/Eastern',
+        }),
+      )
+        .addRRule(new RRule(Frequency.Weekly))
+        .addExDate(new ExDate([DateTime.local(2024, 6, 13, 9, 0, 0)]))
+        .addRDate(new RDate([DateTime.local(2024, 6, 14, 9, 0, 0)]));
+
+      expect(set.includes(DateTime.local(2024, 6, 6, 9, 0, 0))).toBe(true);
+      expect(set.includes(DateTime.local(2024, 6, 6, 10, 0, 0))).toBe(false);
+      expect(set.includes(DateTime.local(2024, 6, 7, 9, 0, 0))).toBe(false);
+      expect(set.includes(DateTime.local(2024, 6, 13, 9, 0, 0))).toBe(false);
+      expect(set.includes(DateTime.local(2024, 6, 14, 9, 0, 0))).toBe(true);
+    });
+  });
+
+  describe('before', () => {
+    it('should return previous occurrence', () => {
+      const set = new RRuleSet(
+        new DtStart({
+          value: DateTime.local(2015, 1, 1, 9, 0, 0),
+          tzid: 'US/Eastern',
+        }),
+      ).addRRule(new RRule(Frequency.Weekly).setInterval(2));
+
+      expect(set.before(DateTime.local(2024, 6, 1, 9, 0, 0))).toEqualPlain(
+        DateTime.local(2024, 5, 23, 9, 0, 0),
+      );
+      expect(
+        set.before(DateTime.local(2024, 5, 23, 9, 0, 0), true),
+      ).toEqualPlain(DateTime.local(2024, 5, 23, 9, 0, 0));
+    });
+
+    it('should return null if there is no previous occurrence', () => {
+      const set = new RRuleSet(
+        new DtStart({
+          value: DateTime.date(1997, 9, 2),
+          tzid: 'US/Eastern',
+        }),
+      ).addRRule(new RRule(Frequency.Daily).setCount(10));
+
+      expect(set.before(DateTime.date(1997, 9, 2))).toBeNull();
+    });
   });
 
   describe('iter', () => {
@@ -313,4 +556,100 @@ describe(RRuleSet, () => {
       expect(dates1[1]).toBe(dates2[1]);
     });
   });
+
+  describe('iterateFrom', () => {
+    it('should iterate from given date', () => {
+      const set = new RRuleSet(
+        new DtStart({
+          value: DateTime.local(2015, 1, 1, 9, 0, 0),
+          tzid: 'US/Eastern',
+        }),
+      ).addRRule(new RRule(Frequency.Daily).setInterval(3));
+
+      const iterator = set.iterateFrom(DateTime.local(2024, 6, 1, 0, 0, 0));
+      const dates: DateTime<Time>[] = [];
+
+      for (const date of iterator) {
+        dates.push(date);
+
+        if (dates.length >= 3) {
+          break;
+        }
+      }
+
+      expect(dates).toEqualPlain([
+        DateTime.local(2024, 6, 3, 9, 0, 0),
+        DateTime.local(2024, 6, 6, 9, 0, 0),
+        DateTime.local(2024, 6, 9, 9, 0, 0),
+      ]);
+    });
+
+    it('should stop at the end of the set', () => {
+      const set = new RRuleSet(
+        new DtStart({
+          value: DateTime.date(1997, 9, 2),
+          tzid: 'US/Eastern',
+        }),
+      ).addRRule(new RRule(Frequency.Daily).setCount(10));
+
+      expect([
+        ...set.iterateFrom(DateTime.date(1997, 9, 9), true),
+      ]).toEqualPlain([
+        DateTime.date(1997, 9, 9),
+        DateTime.date(1997, 9, 10),
+        DateTime.date(1997, 9, 11),
+      ]);
+    });
+  });
+
+  describe('iterateBackwardsFrom', () => {
+    it('should iterate backwards from given date', () => {
+      const set = new RRuleSet(
+        new DtStart({
+          value: DateTime.local(2015, 1, 1, 9, 0, 0),
+          tzid: 'US/Eastern',
+        }),
+      ).addRRule(new RRule(Frequency.Weekly).setInterval(2));
+
+      const dates: DateTime<Time>[] = [];
+
+      for (const date of set.iterateBackwardsFrom(
+        DateTime.local(2024, 6, 1, 9, 0, 0),
+      )) {
+        dates.push(date);
+
+        if (dates.length === 3) {
+          break;
+        }
+      }
+
+      expect(dates).toEqualPlain([
+        DateTime.local(2024, 5, 23, 9, 0, 0),
+        DateTime.local(2024, 5, 9, 9, 0, 0),
+        DateTime.local(2024, 4, 25, 9, 0, 0),
+      ]);
+    });
+
+    it('should honor exdates and rdates down to the start of the set', () => {
+      const set = new RRuleSet(
+        new DtStart({
+          value: DateTime.date(1997, 9, 2),
+          tzid: 'US/Eastern',
+        }),
+      )
+        .addRRule(new RRule(Frequency.Daily).setCount(5))
+        .addExDate(new ExDate([DateTime.date(1997, 9, 4)]))
+        .addRDate(new RDate([DateTime.date(1997, 9, 20)]));
+
+      expect([
+        ...set.iterateBackwardsFrom(DateTime.date(1997, 9, 30)),
+      ]).toEqualPlain([
+        DateTime.date(1997, 9, 20),
+        DateTime.date(1997, 9, 6),
+        DateTime.date(1997, 9, 5),
+        DateTime.date(1997, 9, 3),
+        DateTime.date(1997, 9, 2),
+      ]);
+    });
+  });
 });
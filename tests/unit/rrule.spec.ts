@@ -1,4 +1,4 @@
-import { DateTime, Frequency, RRule, Weekday } from '../../src';
+import { DateTime, DtStart, Frequency, RRule, Weekday } from '../../src';
 import { Month } from '../../src/lib';
 import { describe, it, expect } from 'vitest';
 
@@ -217,4 +217,113 @@ describe(RRule, () => {
       expect(newRrule.byYearday).toEqual([13, 14]);
     });
   });
+
+  describe('validate', () => {
+    it('should return no diagnostics for possible rule', () => {
+      const rrule = RRule.fromString('FREQ=YEARLY;BYMONTH=2;BYMONTHDAY=29');
+
+      expect(rrule.validate()).toEqual([]);
+    });
+
+    it('should detect monthday outside of month', () => {
+      const rrule = RRule.fromString('FREQ=YEARLY;BYMONTH=2;BYMONTHDAY=30');
+
+      expect(rrule.validate()).toEqual([
+        {
+          severity: 'error',
+          code: 'NO_MATCHING_DATE',
+          message: 'No date satisfies BYMONTH=2;BYMONTHDAY=30',
+        },
+      ]);
+    });
+
+    it('should detect monthday outside of months', () => {
+      const rrule = RRule.fromString('FREQ=MONTHLY;BYMONTHDAY=31;BYMONTH=4,6');
+
+      expect(rrule.validate()).toEqual([
+        {
+          severity: 'error',
+          code: 'NO_MATCHING_DATE',
+          message: 'No date satisfies BYMONTH=4,6;BYMONTHDAY=31',
+        },
+      ]);
+    });
+
+    it('should detect nth weekday never falling on monthday', () => {
+      const rrule = RRule.fromString('FREQ=MONTHLY;BYDAY=1MO;BYMONTHDAY=15');
+
+      expect(rrule.validate()).toEqual([
+        {
+          severity: 'error',
+          code: 'NO_MATCHING_DATE',
+          message: 'No date satisfies BYMONTHDAY=15;BYDAY=1MO',
+        },
+      ]);
+    });
+  });
+
+  describe('lint', () => {
+    it('should return no diagnostics for conforming rule', () => {
+      const rrule = RRule.fromString(
+        'FREQ=YEARLY;BYWEEKNO=20;BYDAY=MO;UNTIL=20250101T000000Z',
+      );
+
+      expect(
+        rrule.lint(new DtStart(DateTime.local(2024, 1, 1, 9, 0, 0), 'UTC')),
+      ).toEqual([]);
+    });
+
+    it('should warn about rule parts misused with frequency', () => {
+      const rrule = RRule.fromString(
+        'FREQ=WEEKLY;BYWEEKNO=20;BYYEARDAY=100;BYDAY=1MO',
+      );
+
+      expect(rrule.lint().map((diagnostic) => diagnostic.code)).toEqual([
+        'BYWEEKNO_OUTSIDE_YEARLY',
+        'BYYEARDAY_WITH_FREQUENCY',
+        'NUMERIC_BYDAY_IN_WEEKLY',
+      ]);
+      expect(rrule.lint()[0]).toEqual({
+        severity: 'warning',
+        code: 'BYWEEKNO_OUTSIDE_YEARLY',
+        message: 'BYWEEKNO is only valid with FREQ=YEARLY, not FREQ=WEEKLY',
+      });
+    });
+
+    it('should warn about bysetpos without other by rule', () => {
+      const rrule = RRule.fromString('FREQ=MONTHLY;BYSETPOS=1');
+
+      expect(rrule.lint().map((diagnostic) => diagnostic.code)).toEqual([
+        'BYSETPOS_WITHOUT_BY_RULE',
+      ]);
+    });
+
+    it('should warn about count and until', () => {
+      const rrule = RRule.fromString(
+        'FREQ=DAILY;COUNT=10;UNTIL=20250101T000000Z',
+      );
+
+      expect(rrule.lint().map((diagnostic) => diagnostic.code)).toEqual([
+        'COUNT_AND_UNTIL',
+      ]);
+    });
+
+    it('should warn about local until with zoned dtstart', () => {
+      const rrule = RRule.fromString('FREQ=DAILY;UNTIL=20250101T000000');
+
+      expect(rrule.lint()).toEqual([]);
+      expect(
+        rrule.lint(
+          new DtStart(DateTime.local(2024, 1, 1, 9, 0, 0), 'Europe/London'),
+        ),
+      ).toEqual([
+        {
+          severity: 'warning',
+          code: 'UNTIL_NOT_UTC',
+          message:
+            'UNTIL must be specified in UTC when DTSTART has a TZID, got 20250101T000000',
+        },
+      ]);
+    });
+  });
 });
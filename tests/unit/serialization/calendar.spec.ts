@@ -0,0 +1,268 @@
+import { DateTime, RRuleError, RRuleSet } from '../../../src';
+import { describe, it, expect } from 'vitest';
+
+describe(RRuleSet.fromCalendar, () => {
+  const ics = [
+    'BEGIN:VCALENDAR',
+    'VERSION:2.0',
+    'PRODID:-//Example//EN',
+    'BEGIN:VEVENT',
+    'UID:standup@example.com',
+    'SUMMARY:Standup\\, daily',
+    'DTSTART;TZID=Europe/Paris:20240115T090000',
+    'RRULE:FREQ=DAILY;',
+    ' COUNT=3',
+    'BEGIN:VALARM',
+    'ACTION:DISPLAY',
+    'TRIGGER:-PT15M',
+    'END:VALARM',
+    'END:VEVENT',
+    'BEGIN:VTODO',
+    'UID:todo@example.com',
+    'SUMMARY:Without start',
+    'END:VTODO',
+    'BEGIN:VJOURNAL',
+    'UID:journal@example.com',
+    'DTSTART:20240101T000000Z',
+    'END:VJOURNAL',
+    'END:VCALENDAR',
+  ].join('\r\n');
+
+  it('should return a set per component with DTSTART', () => {
+    const components = RRuleSet.fromCalendar(ics);
+
+    expect(
+      components.map(({ component, uid, summary }) => ({
+        component,
+        uid,
+        summary,
+      })),
+    ).toEqual([
+      {
+        component: 'VEVENT',
+        uid: 'standup@example.com',
+        summary: 'Standup, daily',
+      },
+      { component: 'VJOURNAL', uid: 'journal@example.com' },
+    ]);
+  });
+
+  it('should parse the recurrence of the component', () => {
+    const [event] = RRuleSet.fromCalendar(ics);
+
+    expect(event?.rruleSet.dtstart.tzid).toBe('Europe/Paris');
+    expect(event?.rruleSet.all()).toEqualPlain([
+      DateTime.create(2024, 1, 15, 9, 0, 0, false),
+      DateTime.create(2024, 1, 16, 9, 0, 0, false),
+      DateTime.create(2024, 1, 17, 9, 0, 0, false),
+    ]);
+  });
+
+  it('should keep nested components', () => {
+    const [event] = RRuleSet.fromCalendar(ics);
+
+    expect(event?.components).toEqual([
+      'BEGIN:VALARM\nACTION:DISPLAY\nTRIGGER:-PT15M\nEND:VALARM',
+    ]);
+  });
+
+  it('should accept components without VCALENDAR', () => {
+    const components = RRuleSet.fromCalendar(
+      'BEGIN:VEVENT\nDTSTART:20240101T090000Z\nRRULE:FREQ=DAILY\nEND:VEVENT',
+    );
+
+    expect(components).toHaveLength(1);
+  });
+
+  it.each([
+    {
+      str: 'BEGIN:VEVENT\nDTSTART:20240101T090000Z',
+      message: 'Missing END:VEVENT at line 1, col 1-12',
+    },
+    {
+      str: 'BEGIN:VEVENT\nDTSTART:20240101T090000Z\nEND:VTODO',
+      message: 'Unexpected END:VTODO at line 3, col 1-9',
+    },
+  ])('should throw error on unbalanced components', ({ str, message }) => {
+    const act = () => RRuleSet.fromCalendar(str);
+
+    expect(act).toThrow(RRuleError);
+    expect(act).toThrow(message);
+  });
+});
+
+describe(RRuleSet.prototype.toCalendar, () => {
+  const rruleSet = RRuleSet.fromString(
+    'DTSTART;TZID=Europe/Paris:20240115T090000\nRRULE:FREQ=DAILY;COUNT=3',
+  );
+  const dtstamp = DateTime.create(2024, 1, 1, 0, 0, 0, true);
+
+  it('should wrap the set in VCALENDAR and VEVENT', () => {
+    const ics = rruleSet.toCalendar({
+      uid: 'standup@example.com',
+      summary: 'Standup, daily',
+      dtend: DateTime.create(2024, 1, 15, 9, 15, 0, false),
+      dtstamp,
+    });
+
+    expect(ics).toBe(
+      [
+        'BEGIN:VCALENDAR',
+        'VERSION:2.0',
+        'PRODID:-//rrule-rust//EN',
+        'BEGIN:VTIMEZONE',
+        'TZID:Europe/Paris',
+        'BEGIN:STANDARD',
+        'DTSTART:20231231T000000',
+        'TZOFFSETFROM:+0100',
+        'TZOFFSETTO:+0100',
+        'TZNAME:CET',
+        'END:STANDARD',
+        'BEGIN:DAYLIGHT',
+        'DTSTART:20240331T020000',
+        'TZOFFSETFROM:+0100',
+        'TZOFFSETTO:+0200',
+        'TZNAME:CEST',
+        'END:DAYLIGHT',
+        'BEGIN:STANDARD',
+        'DTSTART:20241027T030000',
+        'TZOFFSETFROM:+0200',
+        'TZOFFSETTO:+0100',
+        'TZNAME:CET',
+        'END:STANDARD',
+        'END:VTIMEZONE',
+        'BEGIN:VEVENT',
+        'UID:standup@example.com',
+        'DTSTAMP:20240101T000000Z',
+        'SUMMARY:Standup\\, daily',
+        'DTSTART;TZID=Europe/Paris:20240115T090000',
+        'DTEND;TZID=Europe/Paris:20240115T091500',
+        'RRULE:FREQ=DAILY;COUNT=3',
+        'END:VEVENT',
+        'END:VCALENDAR',
+        '',
+      ].join('\r\n'),
+    );
+  });
+
+  it('should write nested components', () => {
+    const ics = rruleSet.toCalendar({
+      components: ['BEGIN:VALARM\nACTION:DISPLAY\nTRIGGER:-PT15M\nEND:VALARM'],
+    });
+
+    expect(ics).toContain(
+      [
+        'RRULE:FREQ=DAILY;COUNT=3',
+        'BEGIN:VALARM',
+        'ACTION:DISPLAY',
+        'TRIGGER:-PT15M',
+        'END:VALARM',
+        'END:VEVENT',
+      ].join('\r\n'),
+    );
+  });
+
+  it('should generate UID and DTSTAMP', () => {
+    const ics = rruleSet.toCalendar({ duration: 'PT15M' });
+
+    expect(ics).toMatch(/\r\nUID:\S+\r\n/);
+    expect(ics).toMatch(/\r\nDTSTAMP:\d{8}T\d{6}Z\r\n/);
+    expect(ics).toContain('\r\nDURATION:PT15M\r\n');
+  });
+
+  it('should round-trip through fromCalendar', () => {
+    const ics = rruleSet.toCalendar({ uid: 'a@example.com', summary: 'A' });
+    const [event] = RRuleSet.fromCalendar(ics);
+
+    expect(event?.uid).toBe('a@example.com');
+    expect(event?.summary).toBe('A');
+    expect(event?.rruleSet.all()).toEqualPlain(rruleSet.all());
+  });
+
+  it('should write a VTIMEZONE for every TZID', () => {
+    const set = RRuleSet.fromString(
+      [
+        'DTSTART;TZID=Eastern Standard Time:20240101T090000',
+        'RRULE:FREQ=WEEKLY',
+        'RDATE;TZID=Asia/Tokyo:20240110T090000',
+      ].join('\n'),
+    );
+    const ics = set.toCalendar();
+
+    expect(ics).toContain('\r\nTZID:Eastern Standard Time\r\n');
+    expect(ics).toContain(
+      [
+        'BEGIN:DAYLIGHT',
+        'DTSTART:20240310T020000',
+        'TZOFFSETFROM:-0500',
+        'TZOFFSETTO:-0400',
+        'TZNAME:EDT',
+        'RRULE:FREQ=YEARLY;BYMONTH=3;BYDAY=2SU',
+        'END:DAYLIGHT',
+      ].join('\r\n'),
+    );
+    expect(ics).toContain('\r\nTZID:Asia/Tokyo\r\n');
+    expect(RRuleSet.fromCalendar(ics)[0]?.rruleSet.all(60)).toEqualPlain(
+      set.all(60),
+    );
+  });
+
+  it.each([
+    {
+      rrule: 'FREQ=WEEKLY;UNTIL=20251231T000000Z',
+      expected: 'RRULE:FREQ=YEARLY;UNTIL=20251026T010000Z;BYMONTH=10;BYDAY=-1SU',
+    },
+    {
+      rrule: 'FREQ=WEEKLY;COUNT=100',
+      expected: 'RRULE:FREQ=YEARLY;UNTIL=20251026T010000Z;BYMONTH=10;BYDAY=-1SU',
+    },
+    {
+      rrule: 'FREQ=MONTHLY;COUNT=24;BYDAY=MO;BYSETPOS=1',
+      expected: 'RRULE:FREQ=YEARLY;BYMONTH=10;BYDAY=-1SU',
+    },
+  ])('should end VTIMEZONE with $rrule', ({ rrule, expected }) => {
+    const ics = RRuleSet.fromString(
+      `DTSTART;TZID=Europe/Paris:20240115T090000\nRRULE:${rrule}`,
+    ).toCalendar();
+
+    expect(ics).toContain(`\r\n${expected}\r\n`);
+  });
+
+  it('should write fixed offsets in UTC', () => {
+    const ics = RRuleSet.fromString(
+      'DTSTART:20240115T090000+0530\nRRULE:FREQ=DAILY;COUNT=3',
+    ).toCalendar();
+
+    expect(ics).toContain('\r\nDTSTART:20240115T033000Z\r\n');
+    expect(ics).not.toContain('VTIMEZONE');
+  });
+
+  it.each([
+    {
+      options: { duration: '1 hour' },
+      message: 'Invalid DURATION: 1 hour',
+    },
+    {
+      options: {
+        dtend: DateTime.create(2024, 1, 15, 8, 0, 0, false),
+      },
+      message: 'DTEND must be later than DTSTART',
+    },
+    {
+      options: {
+        dtend: DateTime.create(2024, 1, 15, 10, 0, 0, false),
+        duration: 'PT1H',
+      },
+      message: 'DTEND and DURATION are mutually exclusive',
+    },
+    {
+      options: { dtstamp: DateTime.create(2024, 1, 1, 0, 0, 0, false) },
+      message: 'Invalid DTSTAMP: 20240101T000000',
+    },
+  ])('should throw error on invalid options', ({ options, message }) => {
+    const act = () => rruleSet.toCalendar(options);
+
+    expect(act).toThrow(RRuleError);
+    expect(act).toThrow(message);
+  });
+});
@@ -5,6 +5,7 @@ import {
   DtStart,
   ExDate,
   RDate,
+  RRuleError,
 } from '../../../src';
 import { describe, it, expect } from 'vitest';
 
@@ -183,6 +184,201 @@ describe(RRuleSet, () => {
     expect(act).toThrow('Invalid RRULE: Invalid');
   });
 
+  it.each([
+    ['Invalid', 'PARSE_ERROR'],
+    ['DTSTART;TZID=Invalid:19970907T090000', 'INVALID_TIMEZONE'],
+    ['DTSTART:19970907T090000Z\nRRULE:FREQ=Invalid', 'INVALID_VALUE'],
+    ['RRULE:FREQ=DAILY', 'MISSING_PROPERTY'],
+    ['DTSTART:19970907T090000Z\nDTSTART:19970908T090000Z', 'DUPLICATE_PROPERTY'],
+    ['DTSTART;VALUE=DATE:19970907T090000Z', 'VALUE_TYPE_MISMATCH'],
+  ])('should throw RRuleError with code for %s', (str, code) => {
+    const act = () => RRuleSet.fromString(str);
+
+    expect(act).toThrow(RRuleError);
+    expect(act).toThrow(expect.objectContaining({ code }));
+  });
+
+  it('should report the location of the invalid token', () => {
+    const act = () =>
+      RRuleSet.fromString(
+        'DTSTART:19970907T090000Z\nRRULE:FREQ=MONTHLY;COUNT=3;BYMONTHDAY=32',
+      );
+
+    expect(act).toThrow('Invalid BYMONTHDAY value: 32 at line 2, col 28-40');
+    expect(act).toThrow(
+      expect.objectContaining({
+        location: { line: 2, start: 28, end: 40, token: 'BYMONTHDAY=32' },
+      }),
+    );
+  });
+
+  it('should unfold folded content lines', () => {
+    const set = RRuleSet.fromString(
+      'DTSTART:19970907T090000Z\r\nRRULE:FREQ=WEEKLY;\r\n BYDAY=MO,TU,WE;\r\n\tCOUNT=3',
+    );
+
+    expect(set.toString()).toBe(
+      'DTSTART:19970907T090000Z\nRRULE:FREQ=WEEKLY;COUNT=3;BYDAY=MO,TU,WE',
+    );
+  });
+
+  it.each([
+    '\n    DTSTART:20240101T090000\n    RRULE:FREQ=DAILY;COUNT=3\n  ',
+    'DTSTART:20240101T090000\n  RRULE:FREQ=DAILY;COUNT=3',
+    '\tDTSTART:20240101T090000\n\tRRULE:FREQ=DAILY;\n\t COUNT=3',
+  ])('should trim indented lines instead of unfolding them', (str) => {
+    const set = RRuleSet.fromString(str);
+
+    expect(set.toString()).toBe(
+      'DTSTART:20240101T090000\nRRULE:FREQ=DAILY;COUNT=3',
+    );
+  });
+
+  it('should parse indented template literals', () => {
+    const set = RRuleSet.fromString(`
+      DTSTART:20240101T090000
+      RRULE:FREQ=DAILY;COUNT=3
+    `);
+
+    expect(set.all()).toEqualPlain([
+      DateTime.create(2024, 1, 1, 9, 0, 0, false),
+      DateTime.create(2024, 1, 2, 9, 0, 0, false),
+      DateTime.create(2024, 1, 3, 9, 0, 0, false),
+    ]);
+  });
+
+  it('should fold content lines longer than 75 octets', () => {
+    const str =
+      'DTSTART:19970902T090000Z\nRRULE:FREQ=YEARLY;BYMONTHDAY=1,2,3,4,5,6,7,8,9,10,11,12,13,14,15,16,17,18,19,20,21,22,23,24,25,26,27,28';
+    const set = RRuleSet.fromString(str);
+
+    const folded = set.toString(true);
+
+    expect(folded).toBe(
+      'DTSTART:19970902T090000Z\nRRULE:FREQ=YEARLY;BYMONTHDAY=1,2,3,4,5,6,7,8,9,10,11,12,13,14,15,16,17,18,1\n 9,20,21,22,23,24,25,26,27,28',
+    );
+    expect(RRuleSet.fromString(folded).toString()).toBe(str);
+  });
+
+  it('should parse quoted parameter values', () => {
+    const set = RRuleSet.fromString(
+      'DTSTART;X-URL="http://example.com/a;b";TZID="America/New_York":19970907T090000\nRRULE:FREQ=DAILY;COUNT=3',
+    );
+
+    expect(set.dtstart.tzid).toBe('America/New_York');
+    expect(set.toString()).toBe(
+      'DTSTART;TZID=America/New_York:19970907T090000\nRRULE:FREQ=DAILY;COUNT=3',
+    );
+  });
+
+  it('should throw error on unterminated quoted parameter value', () => {
+    const act = () =>
+      RRuleSet.fromString(
+        'DTSTART;TZID="America/New_York:19970907T090000\nRRULE:FREQ=DAILY',
+      );
+
+    expect(act).toThrow(expect.objectContaining({ code: 'PARSE_ERROR' }));
+  });
+
+  it('should preserve unknown properties, parameters and rule parts', () => {
+    const str = [
+      'DTSTART;TZID=Europe/Paris;X-SOURCE="https://example.com/a;b":19970907T090000',
+      'RRULE;X-RULE=1:FREQ=DAILY;COUNT=3;X-NAME=a,b',
+      "EXDATE;X-NOTE=say ^'hi^':19970908T070000Z",
+      'X-WR-CALNAME:Work',
+      'SUMMARY;LANGUAGE=en:Meeting: weekly',
+    ].join('\n');
+
+    const set = RRuleSet.fromString(str);
+
+    expect(set.toString()).toBe(str);
+    expect(set.dtstart.unknownParameters).toEqual([
+      { name: 'X-SOURCE', values: ['https://example.com/a;b'] },
+    ]);
+    expect(set.rrules[0]?.unknownParameters).toEqual([
+      { name: 'X-RULE', values: ['1'] },
+    ]);
+    expect(set.rrules[0]?.unknownParts).toEqual([
+      { name: 'X-NAME', values: ['a', 'b'] },
+    ]);
+    expect(set.exdates[0]?.unknownParameters).toEqual([
+      { name: 'X-NOTE', values: ['say "hi"'] },
+    ]);
+    expect(set.unknownProperties).toEqual([
+      { name: 'X-WR-CALNAME', parameters: [], value: 'Work' },
+      {
+        name: 'SUMMARY',
+        parameters: [{ name: 'LANGUAGE', values: ['en'] }],
+        value: 'Meeting: weekly',
+      },
+    ]);
+  });
+
+  it('should preserve unknown rule parts with = in their value', () => {
+    const str =
+      'DTSTART:19970907T090000Z\nRRULE:FREQ=DAILY;X-URL=https://example.com/?a=b;X-DATA=YQ==';
+    const set = RRuleSet.fromString(str);
+
+    expect(set.toString()).toBe(str);
+    expect(set.rrules[0]?.unknownParts).toEqual([
+      { name: 'X-URL', values: ['https://example.com/?a=b'] },
+      { name: 'X-DATA', values: ['YQ=='] },
+    ]);
+  });
+
+  it('should emit unknown properties given as options', () => {
+    const set = new RRuleSet({
+      dtstart: new DtStart({
+        value: DateTime.create(1997, 9, 7, 9, 0, 0, true),
+        unknownParameters: [{ name: 'X-SOURCE', values: ['a:b'] }],
+      }),
+      unknownProperties: [
+        { name: 'X-WR-CALNAME', parameters: [], value: 'Work' },
+      ],
+    });
+
+    expect(set.toString()).toBe(
+      'DTSTART;X-SOURCE="a:b":19970907T090000Z\nX-WR-CALNAME:Work',
+    );
+  });
+
+  it.each([
+    { encoded: 'a^nb', decoded: 'a\nb' },
+    { encoded: "say ^'hi^'", decoded: 'say "hi"' },
+    { encoded: 'a^^b', decoded: 'a^b' },
+    { encoded: 'a^b', decoded: 'a^b', reencoded: 'a^^b' },
+  ])(
+    'should round-trip caret-encoded parameter value $encoded',
+    ({ encoded, decoded, reencoded = encoded }) => {
+      const str = `DTSTART;X-NOTE=${encoded}:19970907T090000Z`;
+      const set = RRuleSet.fromString(str);
+
+      expect(set.dtstart.unknownParameters).toEqual([
+        { name: 'X-NOTE', values: [decoded] },
+      ]);
+      expect(set.toString()).toBe(
+        `DTSTART;X-NOTE=${reencoded}:19970907T090000Z`,
+      );
+      expect(
+        RRuleSet.fromString(set.toString()).dtstart.unknownParameters,
+      ).toEqual(set.dtstart.unknownParameters);
+    },
+  );
+
+  it('should encode carriage returns of parameter values as newlines', () => {
+    const set = new RRuleSet({
+      dtstart: new DtStart({
+        value: DateTime.create(1997, 9, 7, 9, 0, 0, true),
+        unknownParameters: [{ name: 'X-NOTE', values: ['a\r\nb\rc'] }],
+      }),
+    });
+
+    expect(set.toString()).toBe('DTSTART;X-NOTE=a^nb^nc:19970907T090000Z');
+    expect(
+      RRuleSet.fromString(set.toString()).dtstart.unknownParameters,
+    ).toEqual([{ name: 'X-NOTE', values: ['a\nb\nc'] }]);
+  });
+
   it('should throw error on invalid frequency', () => {
     const act = () =>
       RRuleSet.fromString(
@@ -273,6 +469,21 @@ describe(RRuleSet, () => {
     );
   });
 
+  it('should throw error when setting from invalid string', () => {
+    const str = 'DTSTART:20240101T090000Z\nRRULE:FREQ=DAILY';
+    const set = RRuleSet.fromString(str);
+    const act = () => set.setFromString('RRULE:FREQ=NOPE');
+
+    expect(act).toThrow(RRuleError);
+    expect(act).toThrow(
+      expect.objectContaining({
+        code: 'INVALID_VALUE',
+        location: { line: 1, start: 7, end: 15, token: 'FREQ=NOPE' },
+      }),
+    );
+    expect(set.toString()).toBe(str);
+  });
+
   it.each([
     {
       dtstart: 'DTSTART:20240323T170000Z',
@@ -50,6 +50,17 @@ describe(RRule, () => {
     expect(act).toThrowError('Invalid RRULE: Invalid');
   });
 
+  it('should report the location of the invalid rule part', () => {
+    const act = () => RRule.fromString('FREQ=DAILY;BYDAY=XX');
+
+    expect(act).toThrow(
+      expect.objectContaining({
+        code: 'INVALID_VALUE',
+        location: { line: 1, start: 12, end: 19, token: 'BYDAY=XX' },
+      }),
+    );
+  });
+
   it('should throw error on invalid individual recurrence rule frequency', () => {
     const act = () => RRule.fromString('FREQ=Invalid');
 
@@ -0,0 +1,85 @@
+import { DateTime, RRuleError, RRuleSet } from '../../src';
+import { describe, it, expect } from 'vitest';
+
+describe('Timestamps', () => {
+  const set = RRuleSet.fromString(
+    [
+      'DTSTART;TZID=Europe/Paris:20240329T090000',
+      'RRULE:FREQ=DAILY;COUNT=4',
+      'EXDATE;TZID=Europe/Paris:20240330T090000',
+    ].join('\n'),
+  );
+  const expected = set.all().map((datetime) => datetime.toTimestamp());
+
+  it('should return all occurrences as epoch milliseconds', () => {
+    const timestamps = set.allTimestamps();
+
+    expect(timestamps).toBeInstanceOf(Float64Array);
+    expect([...timestamps]).toEqual(expected);
+    expect([...timestamps]).toEqual([
+      Date.UTC(2024, 2, 29, 8),
+      Date.UTC(2024, 2, 31, 7),
+      Date.UTC(2024, 3, 1, 7),
+    ]);
+  });
+
+  it('should limit the timestamps', () => {
+    expect([...set.allTimestamps(2)]).toEqual(expected.slice(0, 2));
+    expect([...set.allTimestamps(0)]).toEqual([]);
+  });
+
+  it('should return the timestamps between bounds', () => {
+    const timestamps = set.betweenTimestamps(
+      DateTime.local(2024, 3, 29, 9, 0, 0),
+      DateTime.local(2024, 4, 1, 9, 0, 0),
+    );
+
+    expect([...timestamps]).toEqual([Date.UTC(2024, 2, 31, 7)]);
+    expect([
+      ...set.betweenTimestamps(
+        DateTime.local(2024, 3, 29, 9, 0, 0),
+        DateTime.local(2024, 4, 1, 9, 0, 0),
+        true,
+      ),
+    ]).toEqual(expected);
+  });
+
+  it.each([1, 2, 256])(
+    'should iterate the timestamps in batches of %i',
+    (batchSize) => {
+      expect([...set.iterateTimestamps(batchSize)]).toEqual(expected);
+    },
+  );
+
+  it('should iterate the timestamps of an infinite set', () => {
+    const infinite = RRuleSet.fromString(
+      'DTSTART:20240101T000000Z\nRRULE:FREQ=HOURLY',
+    );
+    const iterator = infinite.iterateTimestamps();
+
+    expect(iterator.next().value).toBe(Date.UTC(2024, 0, 1, 0));
+    expect(iterator.next().value).toBe(Date.UTC(2024, 0, 1, 1));
+  });
+
+  it('should return dates as midnight in the time zone of the set', () => {
+    const dates = RRuleSet.fromString(
+      'DTSTART;VALUE=DATE:20240329\nRRULE:FREQ=DAILY;COUNT=2',
+    );
+
+    expect([...dates.allTimestamps()]).toEqual([
+      Date.UTC(2024, 2, 29),
+      Date.UTC(2024, 2, 30),
+    ]);
+  });
+
+  it('should not return timestamps of floating occurrences', () => {
+    const floating = RRuleSet.fromString(
+      'DTSTART:20240329T090000\nRRULE:FREQ=DAILY;COUNT=2',
+    );
+
+    expect(() => floating.allTimestamps()).toThrow(RRuleError);
+    expect(() => floating.iterateTimestamps()).toThrow(
+      'TZID is required for non-UTC DTSTART',
+    );
+  });
+});
@@ -0,0 +1,312 @@
+import {
+  DateTime,
+  DtStart,
+  ExDate,
+  RRule,
+  RRuleError,
+  RRuleSet,
+} from '../../src';
+import { describe, it, expect } from 'vitest';
+
+const vtimezone = [
+  'BEGIN:VTIMEZONE',
+  'TZID:W. Europe Standard Time',
+  'BEGIN:STANDARD',
+  'DTSTART:16010101T030000',
+  'TZOFFSETFROM:+0200',
+  'TZOFFSETTO:+0100',
+  'RRULE:FREQ=YEARLY;INTERVAL=1;BYDAY=-1SU;BYMONTH=10',
+  'END:STANDARD',
+  'BEGIN:DAYLIGHT',
+  'DTSTART:16010101T020000',
+  'TZOFFSETFROM:+0100',
+  'TZOFFSETTO:+0200',
+  'RRULE:FREQ=YEARLY;INTERVAL=1;BYDAY=-1SU;BYMONTH=3',
+  'END:DAYLIGHT',
+  'END:VTIMEZONE',
+].join('\r\n');
+
+const calendar = (event: string) =>
+  [
+    'BEGIN:VCALENDAR',
+    vtimezone,
+    'BEGIN:VEVENT',
+    event,
+    'END:VEVENT',
+    'END:VCALENDAR',
+  ].join('\r\n');
+
+describe('VTIMEZONE', () => {
+  it.each([
+    { start: '20240330T023000', rrule: 'FREQ=DAILY;COUNT=3' },
+    { start: '20241027T003000', rrule: 'FREQ=HOURLY;COUNT=5' },
+  ])(
+    'should iterate custom time zones like IANA ones ($rrule)',
+    ({ start, rrule }) => {
+      const [event] = RRuleSet.fromCalendar(
+        calendar(
+          `DTSTART;TZID=W. Europe Standard Time:${start}\r\nRRULE:${rrule}`,
+        ),
+      );
+      const berlin = RRuleSet.fromString(
+        `DTSTART;TZID=Europe/Berlin:${start}\nRRULE:${rrule}`,
+      );
+
+      expect(event?.rruleSet.dtstart.tzid).toBe('W. Europe Standard Time');
+      expect(event?.rruleSet.all()).toEqualPlain(berlin.all());
+    },
+  );
+
+  it('should prefer IANA time zones', () => {
+    const [event] = RRuleSet.fromCalendar(
+      calendar('DTSTART;TZID=Europe/Paris:20240101T090000'),
+    );
+
+    expect(event?.rruleSet.timezones).toBeUndefined();
+  });
+
+  it('should write custom time zones as VTIMEZONE', () => {
+    const [event] = RRuleSet.fromCalendar(
+      calendar('DTSTART;TZID=W. Europe Standard Time:20240101T090000'),
+    );
+
+    expect(event?.rruleSet.toCalendar()).toContain(`${vtimezone}\r\n`);
+  });
+
+  it('should resolve TZIDs through the given time zones', () => {
+    const tzid = 'W. Europe Standard Time';
+    const set = new RRuleSet({
+      dtstart: new DtStart(
+        DateTime.create(2024, 3, 30, 9, 0, 0, false),
+        tzid,
+      ),
+      rrules: [RRule.fromString('FREQ=DAILY;COUNT=3')],
+      exdates: [
+        new ExDate([DateTime.create(2024, 3, 31, 9, 0, 0, false)], tzid),
+      ],
+      timezones: [vtimezone],
+    });
+
+    expect(set.all()).toEqualPlain([
+      DateTime.create(2024, 3, 30, 9, 0, 0, false),
+      DateTime.create(2024, 4, 1, 9, 0, 0, false),
+    ]);
+  });
+
+  it('should resolve TZIDs through VTIMEZONE components of the string', () => {
+    const tzid = 'W. Europe Standard Time';
+    const set = RRuleSet.fromString(
+      `${vtimezone}\nDTSTART;TZID=${tzid}:20240330T090000\nRRULE:FREQ=DAILY;COUNT=3`,
+    );
+    const berlin = RRuleSet.fromString(
+      'DTSTART;TZID=Europe/Berlin:20240330T090000\nRRULE:FREQ=DAILY;COUNT=3',
+    );
+
+    expect(set.dtstart.tzid).toBe(tzid);
+    expect(set.timezones).toHaveLength(1);
+    expect(set.all()).toEqualPlain(berlin.all());
+    expect(set.toString()).toBe(
+      `DTSTART;TZID=${tzid}:20240330T090000\nRRULE:FREQ=DAILY;COUNT=3`,
+    );
+  });
+
+  it.each([
+    {
+      str: calendar('DTSTART;TZID=Nowhere:20240101T090000'),
+      message: 'Invalid timezone: Nowhere at line 18, col 9-20',
+    },
+    {
+      str: [
+        'BEGIN:VTIMEZONE',
+        'TZID:Custom',
+        'BEGIN:STANDARD',
+        'DTSTART:16010101T030000',
+        'TZOFFSETFROM:+0200',
+        'END:STANDARD',
+        'END:VTIMEZONE',
+      ].join('\n'),
+      message: 'TZOFFSETTO is required at line 3, col 1-14',
+    },
+    {
+      str: 'BEGIN:VTIMEZONE\nTZID:Custom\nEND:VTIMEZONE',
+      message: 'STANDARD is required at line 1, col 1-15',
+    },
+  ])('should throw error on invalid time zones', ({ str, message }) => {
+    const act = () => RRuleSet.fromCalendar(str);
+
+    expect(act).toThrow(RRuleError);
+    expect(act).toThrow(message);
+  });
+});
+
+describe('Windows time zones', () => {
+  it.each([
+    { windows: 'W. Europe Standard Time', iana: 'Europe/Berlin' },
+    { windows: 'Eastern Standard Time', iana: 'America/New_York' },
+    { windows: 'AUS Eastern Standard Time', iana: 'Australia/Sydney' },
+  ])('should iterate $windows like $iana', ({ windows, iana }) => {
+    const rrule = 'RRULE:FREQ=WEEKLY;COUNT=30';
+    const set = RRuleSet.fromString(
+      `DTSTART;TZID=${windows}:20240301T090000\n${rrule}`,
+    );
+    const expected = RRuleSet.fromString(
+      `DTSTART;TZID=${iana}:20240301T090000\n${rrule}`,
+    );
+
+    expect(set.all()).toEqualPlain(expected.all());
+  });
+
+  it('should preserve Windows TZIDs', () => {
+    const str = [
+      'DTSTART;TZID=Eastern Standard Time:20240101T090000',
+      'RRULE:FREQ=DAILY;UNTIL=20240105T140000Z',
+      'EXDATE;TZID=Eastern Standard Time:20240102T090000',
+      'RDATE;TZID=Pacific Standard Time:20240110T060000',
+    ].join('\n');
+    const set = RRuleSet.fromString(str);
+
+    expect(set.dtstart.tzid).toBe('Eastern Standard Time');
+    expect(set.toString()).toBe(str);
+    expect(set.all()).toEqualPlain([
+      DateTime.create(2024, 1, 1, 9, 0, 0, false),
+      DateTime.create(2024, 1, 3, 9, 0, 0, false),
+      DateTime.create(2024, 1, 4, 9, 0, 0, false),
+      DateTime.create(2024, 1, 5, 9, 0, 0, false),
+      DateTime.create(2024, 1, 10, 9, 0, 0, false),
+    ]);
+  });
+
+  it('should accept Windows TZIDs in constructors', () => {
+    const set = new RRuleSet({
+      dtstart: new DtStart(
+        DateTime.create(2024, 1, 1, 9, 0, 0, false),
+        'Tokyo Standard Time',
+      ),
+      rrules: [RRule.fromString('FREQ=DAILY;COUNT=2')],
+    });
+
+    expect(set.all()).toEqualPlain([
+      DateTime.create(2024, 1, 1, 9, 0, 0, false),
+      DateTime.create(2024, 1, 2, 9, 0, 0, false),
+    ]);
+  });
+
+  it('should prefer VTIMEZONE components over Windows time zones', () => {
+    const [event] = RRuleSet.fromCalendar(
+      calendar('DTSTART;TZID=W. Europe Standard Time:20240101T090000'),
+    );
+
+    expect(event?.rruleSet.timezones).toHaveLength(1);
+    expect(event?.rruleSet.timezones?.[0]).toContain(
+      'TZID:W. Europe Standard Time',
+    );
+  });
+});
+
+describe('Fixed offsets', () => {
+  it('should iterate at fixed offset', () => {
+    const set = RRuleSet.fromString(
+      'DTSTART:20240330T093000+0530\nRRULE:FREQ=DAILY;COUNT=3;BYHOUR=9,23',
+    );
+
+    expect(set.all().map((datetime) => datetime.toString())).toEqual([
+      '20240330T093000+0530',
+      '20240330T233000+0530',
+      '20240331T093000+0530',
+    ]);
+  });
+
+  it('should write occurrences of sets with a time zone with their offset', () => {
+    const set = RRuleSet.fromString(
+      'DTSTART;TZID=US/Eastern:20240115T090000\nRRULE:FREQ=MONTHLY;COUNT=6',
+    );
+    const occurrences = set.all();
+
+    expect(occurrences.map((datetime) => datetime.toString())).toEqual([
+      '20240115T090000-0500',
+      '20240215T090000-0500',
+      '20240315T090000-0400',
+      '20240415T090000-0400',
+      '20240515T090000-0400',
+      '20240615T090000-0400',
+    ]);
+    expect(
+      occurrences.map((datetime) =>
+        DateTime.fromString(datetime.toString()).toTimestamp(),
+      ),
+    ).toEqual(occurrences.map((datetime) => datetime.toTimestamp()));
+  });
+
+  it('should write local datetimes without offset', () => {
+    const set = RRuleSet.fromString(
+      'DTSTART:20240115T090000\nRRULE:FREQ=DAILY;COUNT=2',
+    );
+
+    expect(set.all().map((datetime) => datetime.toString())).toEqual([
+      '20240115T090000',
+      '20240116T090000',
+    ]);
+    expect(DateTime.local(2024, 1, 15, 9, 0, 0).toString()).toBe(
+      '20240115T090000',
+    );
+  });
+
+  it('should write fixed offsets of properties in UTC', () => {
+    const set = RRuleSet.fromString(
+      [
+        'DTSTART:20240330T093000+0530',
+        'RRULE:FREQ=DAILY;UNTIL=20240402T000000-0400',
+        'EXDATE:20240331T093000+0530',
+        'RDATE:20240401T120000+0530',
+      ].join('\n'),
+    );
+
+    expect(set.toString()).toBe(
+      [
+        'DTSTART:20240330T040000Z',
+        'RRULE:FREQ=DAILY;UNTIL=20240402T040000Z',
+        'EXDATE:20240331T040000Z',
+        'RDATE:20240401T063000Z',
+      ].join('\n'),
+    );
+  });
+
+  it('should tell an offset of -1 second from floating time', () => {
+    const set = RRuleSet.fromString(
+      'DTSTART:20240115T143000Z\nRRULE:FREQ=SECONDLY',
+    );
+
+    expect(
+      set.after(DateTime.offset(2024, 1, 15, 14, 30, 0, -1), true)?.toString(),
+    ).toBe('20240115T143001Z');
+  });
+
+  it('should query occurrences with fixed offsets', () => {
+    const set = RRuleSet.fromString(
+      'DTSTART;TZID=Europe/Berlin:20240330T093000\nRRULE:FREQ=DAILY',
+    );
+
+    expect(
+      set
+        .between(
+          DateTime.offset(2024, 3, 31, 13, 0, 0, 19800),
+          DateTime.offset(2024, 4, 1, 3, 30, 0, -3600 * 4),
+          true,
+        )
+        .map((datetime) => datetime.toTimestamp()),
+    ).toEqual([Date.UTC(2024, 2, 31, 7, 30), Date.UTC(2024, 3, 1, 7, 30)]);
+  });
+
+  it('should move fixed offsets into TZID', () => {
+    const exdate = new ExDate(
+      [DateTime.offset(2024, 4, 1, 9, 30, 0, 7200)],
+      'Asia/Tokyo',
+    );
+    const set = RRuleSet.fromString(
+      'DTSTART;TZID=Europe/Berlin:20240330T093000\nRRULE:FREQ=DAILY;COUNT=3',
+    ).addExDate(exdate);
+
+    expect(set.toString()).toContain('EXDATE;TZID=Asia/Tokyo:20240401T163000');
+    expect(set.all()).toHaveLength(2);
+  });
+});
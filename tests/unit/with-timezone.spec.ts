@@ -0,0 +1,97 @@
+import { RRuleError, RRuleSet } from '../../src';
+import { describe, it, expect } from 'vitest';
+
+describe('withTimezone', () => {
+  const set = RRuleSet.fromString(
+    [
+      'DTSTART;TZID=Europe/Paris:20240329T090000',
+      'RRULE:FREQ=DAILY;UNTIL=20240402T070000Z',
+      'EXDATE;TZID=Europe/Paris:20240330T090000',
+      'RDATE:20240331T120000Z',
+    ].join('\n'),
+  );
+
+  it('should keep the wall-clock times', () => {
+    const moved = set.withTimezone('America/New_York', 'wall-clock');
+
+    expect(moved.toString()).toBe(
+      [
+        'DTSTART;TZID=America/New_York:20240329T090000',
+        'RRULE:FREQ=DAILY;UNTIL=20240402T130000Z',
+        'EXDATE;TZID=America/New_York:20240330T090000',
+        'RDATE;TZID=America/New_York:20240331T140000',
+      ].join('\n'),
+    );
+    expect(moved.all().map((datetime) => datetime.toString())).toEqual([
+      '20240329T090000-0400',
+      '20240331T090000-0400',
+      '20240331T140000-0400',
+      '20240401T090000-0400',
+      '20240402T090000-0400',
+    ]);
+  });
+
+  it('should keep the instants', () => {
+    const moved = set.withTimezone('Europe/London', 'instant');
+
+    expect(moved.toString()).toBe(
+      [
+        'DTSTART;TZID=Europe/London:20240329T080000',
+        'RRULE:FREQ=DAILY;UNTIL=20240402T070000Z',
+        'EXDATE;TZID=Europe/London:20240330T080000',
+        'RDATE;TZID=Europe/London:20240331T130000',
+      ].join('\n'),
+    );
+    expect(moved.all().map((datetime) => datetime.toTimestamp())).toEqual(
+      set.all().map((datetime) => datetime.toTimestamp()),
+    );
+  });
+
+  it('should bind floating sets keeping their wall-clock times', () => {
+    const floating = RRuleSet.fromString(
+      [
+        'DTSTART:20240329T090000',
+        'RRULE:FREQ=DAILY;UNTIL=20240331T090000',
+        'EXDATE:20240330T090000',
+      ].join('\n'),
+    );
+
+    expect(floating.withTimezone('Asia/Tokyo', 'wall-clock').toString()).toBe(
+      [
+        'DTSTART;TZID=Asia/Tokyo:20240329T090000',
+        'RRULE:FREQ=DAILY;UNTIL=20240331T000000Z',
+        'EXDATE;TZID=Asia/Tokyo:20240330T090000',
+      ].join('\n'),
+    );
+    expect(() => floating.withTimezone('Asia/Tokyo', 'instant')).toThrow(
+      'TZID is required for non-UTC DTSTART',
+    );
+  });
+
+  it('should return sets of dates as is', () => {
+    const dates = RRuleSet.fromString(
+      'DTSTART;VALUE=DATE:20240329\nRRULE:FREQ=DAILY;COUNT=2',
+    );
+
+    expect(dates.withTimezone('Asia/Tokyo', 'instant').toString()).toBe(
+      dates.toString(),
+    );
+  });
+
+  it('should keep the disambiguation', () => {
+    const moved = set
+      .setDisambiguation('later')
+      .withTimezone('Asia/Tokyo', 'wall-clock');
+
+    expect(moved.disambiguation).toBe('later');
+  });
+
+  it('should throw on an unknown time zone or mode', () => {
+    expect(() => set.withTimezone('Mars/Olympus', 'instant')).toThrow(
+      RRuleError,
+    );
+    expect(() =>
+      set.withTimezone('Asia/Tokyo', 'sideways' as 'instant'),
+    ).toThrow(RRuleError);
+  });
+});